@@ -39,7 +39,7 @@ impl<T: Real> NormalCone<T> {
 
     /// Max distance (radians) from unit vector to cone (distant side).
     pub fn max_angle_to_vec(&self, normal: &Vec3<T>) -> T {
-        let dotp: T = self.center_normal.dot(&normal);
+        let dotp: T = self.center_normal.dot(normal);
         let one: T = One::one();
         let center_angle = if dotp >= one {
             Zero::zero()
@@ -37,7 +37,7 @@ impl<T: Real> Plane3<T> {
 
     /// Signed distance of a point from the plane.
     pub fn signed_dist(&self, pt: &Vec3<T>) -> T {
-        self.n.dot(&pt) + self.d
+        self.n.dot(pt) + self.d
     }
 
     /// Signed distance to the origin. Same as `self.signed_dist(origin)`.
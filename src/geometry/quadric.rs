@@ -39,7 +39,7 @@ impl<Scalar: Real> Quadric<Scalar> {
 
     /// Quadric representing distance from plane defined by normal n and point pt.
     pub fn from_plane(n: &Vec3<Scalar>, pt: &Vec3<Scalar>) -> Self {
-        Self::from_plane_coeffs(n[0], n[1], n[2], -n.dot(&pt))
+        Self::from_plane_coeffs(n[0], n[1], n[2], -n.dot(pt))
     }
 
     /// Sets quadric in the symmetric upper-triangular matrix form.
@@ -100,7 +100,7 @@ impl<Scalar: Real> Quadric<Scalar> {
     #[allow(missing_docs)] pub fn ww(&self) -> Scalar { self.0[9] }
 }
 
-impl<'a, 'b, Scalar> Quadric<Scalar>
+impl<Scalar> Quadric<Scalar>
 where
     Scalar: Mul<Output = Scalar> + Add<Output = Scalar> + One + Copy + Real,
 {
@@ -108,7 +108,7 @@ where
     #[rustfmt::skip]
     fn eval_coords(&self, x: Scalar, y: Scalar, z: Scalar, w: Scalar) -> Scalar {
         let two = Scalar::from_f32(2.0f32);
-        let ref m = self.0;
+        let m = &self.0;
         m[0] * x*x + m[1] * two*x*y + m[2] * two*x*z + m[3] * two*x*w
                    + m[4]      *y*y + m[5] * two*y*z + m[6] * two*y*w
                                     + m[7] *     z*z + m[8] * two*z*w
@@ -130,7 +130,7 @@ impl<Scalar: Real + Mul<Output = Scalar>> Quadric<Scalar> {
     /// Multiplies a quadric in-place by a scalar.
     pub fn mul_by(&mut self, s: Scalar) {
         for val in self.0.iter_mut() {
-            *val = *val * s;
+            *val *= s;
         }
     }
 }
@@ -158,7 +158,7 @@ where
 }
 
 // Multiplication by scalar.
-impl<'a, Scalar: Mul + Copy> Mul<Scalar> for &'a Quadric<Scalar>
+impl<Scalar: Mul + Copy> Mul<Scalar> for &Quadric<Scalar>
 where
     <Scalar as Mul>::Output: Copy,
 {
@@ -181,7 +181,7 @@ where
 }
 
 // Matrix multiplication by vec4.
-impl<'a, 'b, Scalar> Mul<&'b Vec4<Scalar>> for &'a Quadric<Scalar>
+impl<'b, Scalar> Mul<&'b Vec4<Scalar>> for &Quadric<Scalar>
 where
     Scalar: Mul<Output = Scalar> + Add<Output = Scalar> + Copy,
 {
@@ -198,7 +198,7 @@ where
 }
 
 // Matrix multiplication by vec4.
-impl<'a, Scalar> Mul<Vec4<Scalar>> for &'a Quadric<Scalar>
+impl<Scalar> Mul<Vec4<Scalar>> for &Quadric<Scalar>
 where
     Scalar: Mul<Output = Scalar> + Add<Output = Scalar> + Copy,
 {
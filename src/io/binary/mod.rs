@@ -11,8 +11,6 @@ mod geometry;
 mod primitives;
 mod status;
 mod string;
-pub use self::bitvec::*;
-pub use self::geometry::*;
-pub use self::primitives::*;
-pub use self::status::*;
-pub use self::string::*;
+// Kept as part of the public API even though the modules currently only hold trait impls.
+#[allow(unused_imports)]
+pub use self::{bitvec::*, geometry::*, primitives::*, status::*, string::*};
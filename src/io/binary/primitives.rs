@@ -76,7 +76,6 @@ binary_impl_primitive!(f64, read_f64, write_f64);
 mod test {
     use crate::io::binary::test;
     use crate::io::binary::traits::Endian::{Big, Little};
-    use std::mem::transmute;
 
     #[test]
     fn test_store() {
@@ -113,8 +112,8 @@ mod test {
             &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
         );
 
-        let float32: f32 = unsafe { transmute(0x3e124925u32) };
-        let float64: f64 = unsafe { transmute(0x3e12492589abcdefu64) };
+        let float32: f32 = f32::from_bits(0x3e124925u32);
+        let float64: f64 = f64::from_bits(0x3e12492589abcdefu64);
         test::test_store(Little, &float32, &[0x25, 0x49, 0x12, 0x3e]);
         test::test_store(Big, &float32, &[0x3e, 0x12, 0x49, 0x25]);
         test::test_store(
@@ -168,8 +167,8 @@ mod test {
             &0x0123456789abcdefi64,
         );
 
-        let float32: f32 = unsafe { transmute(0x3e124925u32) };
-        let float64: f64 = unsafe { transmute(0x3e12492589abcdefu64) };
+        let float32: f32 = f32::from_bits(0x3e124925u32);
+        let float64: f64 = f64::from_bits(0x3e12492589abcdefu64);
         test::test_restore(Little, &[0x25, 0x49, 0x12, 0x3e], || 0.0, &float32);
         test::test_restore(Big, &[0x3e, 0x12, 0x49, 0x25], || 0.0, &float32);
         test::test_restore(
@@ -224,8 +223,9 @@ mod test_vec {
             &[0x01, 0x23, 0x45, 0x67, 0x13, 0x57, 0x02, 0x46],
         );
 
-        let vecf =
-            Vec::<f32>::from(&unsafe { transmute::<_, [f32; 2]>([0x3e124925u32, 0x6537851a]) }[..]);
+        let vecf = Vec::<f32>::from(
+            &unsafe { transmute::<[u32; 2], [f32; 2]>([0x3e124925u32, 0x6537851a]) }[..],
+        );
         test::test_store(
             Little,
             &vecf,
@@ -238,7 +238,9 @@ mod test_vec {
         );
 
         let vecd = Vec::<f64>::from(
-            &unsafe { transmute::<_, [f64; 2]>([0x3e12492589abcdefu64, 0x3fae1e1f3ac15743]) }[..],
+            &unsafe {
+                transmute::<[u64; 2], [f64; 2]>([0x3e12492589abcdefu64, 0x3fae1e1f3ac15743])
+            }[..],
         );
         test::test_store(
             Little,
@@ -293,8 +295,9 @@ mod test_vec {
             &vec![0x01234567i32, 0x13570246],
         );
 
-        let vecf =
-            Vec::<f32>::from(&unsafe { transmute::<_, [f32; 2]>([0x3e124925u32, 0x6537851a]) }[..]);
+        let vecf = Vec::<f32>::from(
+            &unsafe { transmute::<[u32; 2], [f32; 2]>([0x3e124925u32, 0x6537851a]) }[..],
+        );
         test::test_restore(
             Little,
             &[0x25, 0x49, 0x12, 0x3e, 0x1a, 0x85, 0x37, 0x65],
@@ -309,7 +312,9 @@ mod test_vec {
         );
 
         let vecd = Vec::<f64>::from(
-            &unsafe { transmute::<_, [f64; 2]>([0x3e12492589abcdefu64, 0x3fae1e1f3ac15743]) }[..],
+            &unsafe {
+                transmute::<[u64; 2], [f64; 2]>([0x3e12492589abcdefu64, 0x3fae1e1f3ac15743])
+            }[..],
         );
         test::test_restore(
             Little,
@@ -17,7 +17,7 @@ impl Binary for String {
 
     fn store_endian<B: ByteOrder>(&self, writer: &mut dyn Write) -> Result<usize> {
         let len = self.len();
-        if len > u16::max_value() as usize {
+        if len > u16::MAX as usize {
             return Err(Error::StringExceeds64k);
         }
         // TODO: OpenMesh has a bug where len is double-swapped.
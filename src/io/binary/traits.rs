@@ -109,6 +109,7 @@ impl<T: Binary> Binary for Vec<T> {
 }
 
 #[cfg(test)]
+#[allow(missing_docs)]
 pub mod test {
     use crate::io::binary::traits::Endian;
     use crate::io::binary::{Binary, UNKNOWN_SIZE};
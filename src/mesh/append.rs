@@ -0,0 +1,250 @@
+//! Appending the items of one mesh to another.
+use crate::mesh::handle_map::{AppendMap, HandleMap};
+use crate::mesh::item_handle::MeshItemHandle;
+use crate::mesh::items::{Edge, Face, Halfedge, Vertex};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::{Handle, PropertyContainer, Size};
+
+/// How `Mesh::append_with()` treats the properties of the appended mesh that have no property of
+/// the same name and type in the target mesh, which includes all unnamed properties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnmatchedProperties {
+    /// Discards them.
+    Drop,
+    /// Adds them to the target mesh, with default values for its existing items.
+    Add,
+    /// Fails with `TopologyError::UnmatchedProperty` without changing the target mesh.
+    Fail,
+}
+
+/// Maps each handle with index `i` to the one with index `i + offset`, keeping invalid handles.
+fn offset_map<H: Handle>(len: Size, offset: Size) -> HandleMap<H> {
+    HandleMap::from_vec((0..len).map(|i| H::from_index(i + offset)).collect())
+}
+
+impl Mesh {
+    /// Appends all items of `other` to `self` like `Mesh::append_with()`, adding the unmatched
+    /// properties of `other` to `self`.
+    pub fn append(&mut self, other: &Mesh) -> AppendMap {
+        self.append_with(other, UnmatchedProperties::Add)
+            .expect("Appending with UnmatchedProperties::Add does not fail")
+    }
+
+    /// Appends all items of `other`, including DELETED ones, to `self`, and returns the map
+    /// from the handles of `other` to the new handles in `self`.
+    ///
+    /// Each named property of `other` is appended to the property of `self` with the same name
+    /// and type. The others, including all unnamed ones, are handled according to `policy`,
+    /// except that the status of each item type of `other` is always kept by requesting it on
+    /// `self`. Properties of `self` without a counterpart in `other` get default values for the
+    /// new items. Mesh properties of `self` are kept as they are.
+    pub fn append_with(&mut self, other: &Mesh, policy: UnmatchedProperties) -> Result<AppendMap> {
+        if policy == UnmatchedProperties::Fail {
+            check_matched(&self.v_props, &other.v_props)?;
+            check_matched(&self.h_props, &other.h_props)?;
+            check_matched(&self.e_props, &other.e_props)?;
+            check_matched(&self.f_props, &other.f_props)?;
+        }
+        if other.get_vertex_status().is_some() {
+            self.request_vertex_status();
+        }
+        if other.get_halfedge_status().is_some() {
+            self.request_halfedge_status();
+        }
+        if other.get_edge_status().is_some() {
+            self.request_edge_status();
+        }
+        if other.get_face_status().is_some() {
+            self.request_face_status();
+        }
+
+        let (nv, ne, nf) = (
+            self.vertices.len() as Size,
+            self.edges.len() as Size,
+            self.faces.len() as Size,
+        );
+        let map = AppendMap {
+            vertices: offset_map(other.vertices.len() as Size, nv),
+            halfedges: offset_map(2 * other.edges.len() as Size, 2 * ne),
+            edges: offset_map(other.edges.len() as Size, ne),
+            faces: offset_map(other.faces.len() as Size, nf),
+        };
+        let halfedge = |halfedge: &Halfedge| Halfedge {
+            fh: map.faces.get(halfedge.fh),
+            vh: map.vertices.get(halfedge.vh),
+            hnext: map.halfedges.get(halfedge.hnext),
+            hprev: map.halfedges.get(halfedge.hprev),
+        };
+        self.vertices
+            .extend(other.vertices.iter().map(|vertex| Vertex {
+                hh: map.halfedges.get(vertex.hh),
+            }));
+        self.edges.extend(
+            other
+                .edges
+                .iter()
+                .map(|edge| Edge([halfedge(&edge.0[0]), halfedge(&edge.0[1])])),
+        );
+        self.faces.extend(other.faces.iter().map(|face| Face {
+            hh: map.halfedges.get(face.hh),
+        }));
+
+        let add = policy == UnmatchedProperties::Add;
+        let (mv, me, mf) = (map.vertices.len(), map.edges.len(), map.faces.len());
+        self.v_props.append(nv, &other.v_props, mv, add);
+        self.h_props.append(2 * ne, &other.h_props, 2 * me, add);
+        self.e_props.append(ne, &other.e_props, me, add);
+        self.f_props.append(nf, &other.f_props, mf, add);
+        Ok(map)
+    }
+}
+
+/// Fails if any property of `other` other than the status has no counterpart in `props`.
+fn check_matched<H: MeshItemHandle>(
+    props: &PropertyContainer<H>,
+    other: &PropertyContainer<H>,
+) -> Result<()> {
+    let status = H::with_prefix("status");
+    match props
+        .unmatched_names(other)
+        .into_iter()
+        .find(|name| *name != status)
+    {
+        Some(name) => Err(TopologyError::UnmatchedProperty(name)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UnmatchedProperties;
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, fan, grid, request_status};
+    use crate::mesh::Mesh;
+    use crate::property::{Handle, VPropHandle};
+
+    #[test]
+    fn append() {
+        let (mut mesh, _) = grid(1);
+        let (other, v, f) = fan(3);
+        let map = mesh.append(&other);
+        assert_eq!(mesh.vertices().len(), 8);
+        assert_eq!(mesh.edges().len(), 10);
+        assert_eq!(mesh.faces().len(), 4);
+        assert_consistent(&mesh);
+        assert_eq!(map.vertices.get(v[1]), VertexHandle::from_index(5));
+        assert_eq!(map.faces.get(f[2]), FaceHandle::from_index(3));
+        for &vh in &v {
+            assert_eq!(mesh.point(map.vertices.get(vh)), other.point(vh));
+        }
+        for &fh in &f {
+            let mut vhs: Vec<_> = other.fv_ccw_iter(fh).collect();
+            map.vertices.update(&mut vhs);
+            assert_eq!(mesh.fv_ccw_iter(map.faces.get(fh)).collect::<Vec<_>>(), vhs);
+        }
+        assert_eq!(mesh.boundary_loops().len(), 2);
+    }
+
+    #[test]
+    fn append_properties() {
+        let (mut mesh, _) = grid(1);
+        let (mut other, _, _) = fan(3);
+        let shared = mesh.v_props_mut().add::<u32>(Some("shared".into()));
+        let other_shared = other.v_props_mut().add::<u32>(Some("shared".into()));
+        let own = mesh.f_props_mut().add::<f64>(Some("own".into()));
+        let other_only = other.f_props_mut().add::<u32>(Some("other".into()));
+        for i in 0..4 {
+            let vh = VertexHandle::from_index(i);
+            other.v_props_mut().get_mut(other_shared).unwrap()[vh] = 10 + i;
+        }
+        for i in 0..3 {
+            let fh = FaceHandle::from_index(i);
+            other.f_props_mut().get_mut(other_only).unwrap()[fh] = 20 + i;
+        }
+        request_status(&mut other);
+        other.delete_face(FaceHandle::from_index(0), false).unwrap();
+
+        let n_props = mesh.f_props.len();
+        assert_eq!(
+            mesh.append_with(&other, UnmatchedProperties::Fail),
+            Err(TopologyError::UnmatchedProperty("other".into()))
+        );
+        assert_eq!(mesh.vertices().len(), 4);
+        assert_eq!(mesh.f_props.len(), n_props);
+
+        let mut dropped = mesh.clone();
+        dropped
+            .append_with(&other, UnmatchedProperties::Drop)
+            .unwrap();
+        assert!(!dropped.f_props().handle::<u32>("other").is_valid());
+        assert!(dropped.get_face_status().unwrap()[FaceHandle::from_index(1)].deleted());
+
+        let map = mesh.append(&other);
+        let shared = mesh.v_props().get(shared).unwrap();
+        let values = mesh
+            .vertices()
+            .iter_all()
+            .map(|vh| shared[vh])
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![0, 0, 0, 0, 10, 11, 12, 13]);
+        let own = mesh.f_props().get(own).unwrap();
+        assert_eq!(own.storage, vec![0.0; 4]);
+        let other_only = mesh.f_props().handle::<u32>("other");
+        let other_only = mesh.f_props().get(other_only).unwrap();
+        assert_eq!(other_only.storage, vec![0, 20, 21, 22]);
+        assert!(
+            mesh.get_face_status().unwrap()[map.faces.get(FaceHandle::from_index(0))].deleted()
+        );
+        assert_eq!(mesh.faces().iter().count(), 3);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn append_unnamed_properties() {
+        let (mut mesh, _) = grid(1);
+        let (mut other, _, _) = fan(3);
+        let own = mesh.v_props_mut().add::<u32>(None);
+        let other_prop = other.v_props_mut().add::<u32>(None);
+        other.v_props_mut().get_mut(other_prop).unwrap()[VertexHandle::from_index(0)] = 7;
+
+        assert_eq!(
+            mesh.append_with(&other, UnmatchedProperties::Fail),
+            Err(TopologyError::UnmatchedProperty("<unknown>".into()))
+        );
+        let mut dropped = mesh.clone();
+        let n_props = dropped.v_props.len();
+        dropped
+            .append_with(&other, UnmatchedProperties::Drop)
+            .unwrap();
+        assert_eq!(dropped.v_props.len(), n_props);
+        assert_eq!(dropped.v_props().get(own).unwrap().storage, vec![0; 8]);
+
+        // The values of `other` go to a new property, in the slot after `own`.
+        let n_props = mesh.v_props.len();
+        mesh.append(&other);
+        assert_eq!(mesh.v_props.len(), n_props + 1);
+        assert_eq!(mesh.v_props().get(own).unwrap().storage, vec![0; 8]);
+        let added = VPropHandle::<u32>::from_index(own.index() + 1);
+        let added = mesh.v_props().get(added).unwrap();
+        assert_eq!(added.storage, vec![0, 0, 0, 0, 7, 0, 0, 0]);
+    }
+
+    #[test]
+    fn append_to_empty() {
+        let (mut other, _) = grid(2);
+        request_status(&mut other);
+        other.set_point(VertexHandle::from_index(4), Vec3::new(0.5, 0.5, 1.0));
+        let mut mesh = Mesh::new();
+        let map = mesh.append(&other);
+        assert_eq!(
+            map.vertices.as_slice(),
+            &(0..9).map(VertexHandle::from_index).collect::<Vec<_>>()[..]
+        );
+        assert_eq!(mesh.points(), other.points());
+        assert!(mesh.get_edge_status().is_some());
+        assert_consistent(&mesh);
+    }
+}
@@ -0,0 +1,104 @@
+//! Standard properties that are always present on a `Mesh`, in the spirit of OpenMesh's
+//! `AttribKernel`. Currently, this consists of the vertex positions ("v:points").
+use crate::geometry::vector::Vec3;
+use crate::mesh::item_handle::{MeshItemHandle, VertexHandle};
+use crate::mesh::Mesh;
+use crate::property::{PropertyContainer, PropertyList, VPropHandle};
+
+/// Adds the vertex position property to an (empty) vertex property container.
+pub(crate) fn add_points_prop(
+    v_props: &mut PropertyContainer<VertexHandle>,
+) -> VPropHandle<Vec3<f64>> {
+    v_props.add::<Vec3<f64>>(Some(VertexHandle::with_prefix("points")), 0)
+}
+
+impl Mesh {
+    /// The vertex position property list. It is added on construction and never removed.
+    fn points_prop(&self) -> &PropertyList<Vec3<f64>, VertexHandle> {
+        self.v_props
+            .get(self.points)
+            .expect("Vertex position property must always exist.")
+    }
+
+    /// The vertex position property list, mutably.
+    fn points_prop_mut(&mut self) -> &mut PropertyList<Vec3<f64>, VertexHandle> {
+        self.v_props
+            .get_mut(self.points)
+            .expect("Vertex position property must always exist.")
+    }
+
+    /// Adds a new vertex at position `p` and returns its handle.
+    pub fn add_vertex(&mut self, p: Vec3<f64>) -> VertexHandle {
+        let vh = self.vertices_mut().append();
+        self.set_point(vh, p);
+        vh
+    }
+
+    /// Position of the vertex `vh`. Panics if `vh` is out of bounds.
+    pub fn point(&self, vh: VertexHandle) -> Vec3<f64> {
+        self.points_prop()[vh]
+    }
+
+    /// Sets the position of the vertex `vh`. Panics if `vh` is out of bounds.
+    pub fn set_point(&mut self, vh: VertexHandle, p: Vec3<f64>) {
+        self.points_prop_mut()[vh] = p;
+    }
+
+    /// Positions of all vertices, indexed by vertex handle index.
+    pub fn points(&self) -> &[Vec3<f64>] {
+        &self.points_prop().storage
+    }
+
+    /// Positions of all vertices mutably, indexed by vertex handle index.
+    pub fn points_mut(&mut self) -> &mut [Vec3<f64>] {
+        &mut self.points_prop_mut().storage
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::VertexHandle;
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    #[test]
+    fn add_vertex() {
+        let mut mesh = Mesh::new();
+        let vh0 = mesh.add_vertex(Vec3::new(1.0, 2.0, 3.0));
+        let vh1 = mesh.add_vertex(Vec3::new(4.0, 5.0, 6.0));
+        assert_eq!(vh0, VertexHandle::from_index(0));
+        assert_eq!(vh1, VertexHandle::from_index(1));
+        assert_eq!(mesh.vertices().len(), 2);
+        assert_eq!(mesh.point(vh0), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(mesh.point(vh1), Vec3::new(4.0, 5.0, 6.0));
+
+        mesh.set_point(vh0, Vec3::new(-1.0, -2.0, -3.0));
+        assert_eq!(
+            mesh.points(),
+            &[Vec3::new(-1.0, -2.0, -3.0), Vec3::new(4.0, 5.0, 6.0)]
+        );
+    }
+
+    #[test]
+    fn points_resize_with_vertices() {
+        let mut mesh = Mesh::new();
+        let vh = mesh.vertices_mut().append();
+        assert_eq!(mesh.point(vh), Vec3::new(0.0, 0.0, 0.0));
+        mesh.resize(5, 0, 0);
+        assert_eq!(mesh.points().len(), 5);
+        mesh.points_mut()[4] = Vec3::new(1.0, 1.0, 1.0);
+        assert_eq!(
+            mesh.point(VertexHandle::from_index(4)),
+            Vec3::new(1.0, 1.0, 1.0)
+        );
+        mesh.clear();
+        assert!(mesh.points().is_empty());
+    }
+
+    #[test]
+    fn points_named_prop() {
+        let mesh = Mesh::new();
+        assert!(mesh.v_props().handle::<Vec3<f64>>("v:points").is_valid());
+    }
+}
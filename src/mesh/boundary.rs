@@ -0,0 +1,120 @@
+//! Boundary loops, i.e. the cycles of halfedges without a face around holes in the mesh.
+use crate::geometry::vector::Vec3;
+use crate::mesh::item_handle::HalfedgeHandle;
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+impl Mesh {
+    /// The boundary halfedges, i.e. those without a face, whose edges are not DELETED.
+    pub(crate) fn boundary_halfedges(&self) -> impl Iterator<Item = HalfedgeHandle> + '_ {
+        self.halfedges()
+            .iter_all()
+            .filter(move |&hh| !self.is_deleted(self.edge(hh)) && self.is_boundary_halfedge(hh))
+    }
+
+    /// Whether the mesh has no boundary, i.e. every edge that is not DELETED has a face on both
+    /// sides. Isolated vertices do not count as boundary.
+    pub fn is_closed(&self) -> bool {
+        self.boundary_halfedges().next().is_none()
+    }
+
+    /// The loops of boundary halfedges (halfedges without a face), each in the order of
+    /// `Mesh::next_halfedge()`. Each loop bounds a hole or the outer boundary of the mesh.
+    pub fn boundary_loops(&self) -> Vec<Vec<HalfedgeHandle>> {
+        let mut visited = vec![false; self.halfedges().len() as usize];
+        let mut loops = Vec::new();
+        for start in self.boundary_halfedges() {
+            if visited[start.index_us()] {
+                continue;
+            }
+            let mut hhs = Vec::new();
+            let mut hh = start;
+            loop {
+                visited[hh.index_us()] = true;
+                hhs.push(hh);
+                hh = self.next_halfedge(hh);
+                if hh == start {
+                    break;
+                }
+            }
+            loops.push(hhs);
+        }
+        loops
+    }
+
+    /// The total length of the halfedges in `hhs`, e.g. a loop from `boundary_loops()`.
+    pub fn boundary_loop_perimeter(&self, hhs: &[HalfedgeHandle]) -> f64 {
+        hhs.iter()
+            .map(|&hh| (self.point(self.to_vertex(hh)) - self.point(self.from_vertex(hh))).norm())
+            .sum()
+    }
+
+    /// Estimates the area enclosed by the closed loop of halfedges `hhs`, e.g. a loop from
+    /// `boundary_loops()`, as the magnitude of its vector area. This is exact for planar loops
+    /// and the area projected onto the best-fit plane otherwise.
+    pub fn boundary_loop_area(&self, hhs: &[HalfedgeHandle]) -> f64 {
+        let vector_area: Vec3<f64> = hhs
+            .iter()
+            .map(|&hh| {
+                let p0 = self.point(self.from_vertex(hh));
+                let p1 = self.point(self.to_vertex(hh));
+                p0.cross(&p1)
+            })
+            .sum();
+        vector_area.norm() / 2.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mesh::test_util::{fan, grid, mesh_with_vertices, request_status};
+
+    #[test]
+    fn boundary_loops() {
+        let (mesh, v) = grid(2);
+        assert!(!mesh.is_closed());
+        let loops = mesh.boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), 8);
+        for (i, &hh) in loops[0].iter().enumerate() {
+            assert!(mesh.is_boundary(hh));
+            assert_eq!(mesh.next_halfedge(hh), loops[0][(i + 1) % 8]);
+        }
+        assert!(loops[0].iter().all(|&hh| mesh.to_vertex(hh) != v[4]));
+        assert_eq!(mesh.boundary_loop_perimeter(&loops[0]), 8.0);
+        assert_eq!(mesh.boundary_loop_area(&loops[0]), 4.0);
+    }
+
+    #[test]
+    fn holes() {
+        // Deleting the middle face of a 3x3 grid leaves a unit square hole.
+        let (mut mesh, v) = grid(3);
+        request_status(&mut mesh);
+        let hh = mesh.find_halfedge(v[5], v[6]);
+        mesh.delete_face(mesh.face(hh), false).unwrap();
+        let mut loops = mesh.boundary_loops();
+        loops.sort_by_key(|hhs| hhs.len());
+        assert_eq!(loops.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 12]);
+        assert_eq!(mesh.boundary_loop_perimeter(&loops[0]), 4.0);
+        assert_eq!(mesh.boundary_loop_area(&loops[0]), 1.0);
+        assert_eq!(mesh.boundary_loop_perimeter(&loops[1]), 12.0);
+        assert_eq!(mesh.boundary_loop_area(&loops[1]), 9.0);
+    }
+
+    #[test]
+    fn closed() {
+        // Tetrahedron.
+        let (mut mesh, v) = mesh_with_vertices(4);
+        for face in &[[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]] {
+            mesh.add_face(&[v[face[0]], v[face[1]], v[face[2]]])
+                .unwrap();
+        }
+        mesh.add_vertex(Default::default());
+        assert!(mesh.is_closed());
+        assert!(mesh.boundary_loops().is_empty());
+
+        let (mesh, _, _) = fan(4);
+        assert!(!mesh.is_closed());
+        assert_eq!(mesh.boundary_loops().len(), 1);
+    }
+}
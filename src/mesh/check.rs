@@ -0,0 +1,444 @@
+//! Topology consistency checks, in the spirit of OpenMesh's `MeshCheckerT`.
+use std::fmt;
+
+use crate::mesh::item_handle::{
+    EdgeHandle, FaceHandle, HalfedgeHandle, MeshItemHandle, VertexHandle,
+};
+use crate::mesh::Mesh;
+use crate::property::{Handle, ItemHandle, PropertyContainer, Size};
+
+/// Violation of a mesh invariant found by `Mesh::check_topology()`.
+///
+/// A handle is "invalid" below if it is out of bounds or refers to a DELETED item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TopologyViolation {
+    /// The vertex's halfedge is invalid, or is missing although the vertex has edges.
+    VertexHalfedgeInvalid(VertexHandle),
+    /// The vertex's halfedge does not start at the vertex.
+    VertexHalfedgeNotOutgoing(VertexHandle),
+    /// The vertex is on the boundary, but its halfedge is not a boundary halfedge.
+    VertexHalfedgeNotBoundary(VertexHandle),
+    /// Rotating around the vertex does not lead back to its halfedge.
+    VertexCycleOpen(VertexHandle),
+    /// The halfedge's to-vertex is invalid.
+    HalfedgeVertexInvalid(HalfedgeHandle),
+    /// The halfedge's next or previous halfedge is invalid.
+    HalfedgeNextInvalid(HalfedgeHandle),
+    /// The halfedge is not the previous halfedge of its next halfedge, or not the next halfedge
+    /// of its previous halfedge.
+    HalfedgeNextPrevMismatch(HalfedgeHandle),
+    /// The halfedge's next halfedge does not start at its to-vertex, i.e. the opposite of the
+    /// next halfedge points to a different vertex.
+    HalfedgeNotConnected(HalfedgeHandle),
+    /// The halfedge's face is invalid.
+    HalfedgeFaceInvalid(HalfedgeHandle),
+    /// The halfedge's next halfedge belongs to a different face.
+    HalfedgeFaceMismatch(HalfedgeHandle),
+    /// Both halfedges of the edge point to the same vertex.
+    DegenerateEdge(EdgeHandle),
+    /// The face's halfedge is invalid or belongs to another face.
+    FaceHalfedgeInvalid(FaceHandle),
+    /// Following the next halfedges from the face's halfedge does not lead back to it.
+    FaceCycleOpen(FaceHandle),
+    /// The face has fewer than 3 halfedges.
+    DegenerateFace(FaceHandle),
+    /// The property list's length differs from the number of items of its type.
+    PropertyLength {
+        /// Item type ("vertex", "halfedge", etc.).
+        item: &'static str,
+        /// Property name.
+        name: String,
+        /// Length of the property list.
+        len: usize,
+        /// Number of items.
+        expected: usize,
+    },
+}
+
+impl fmt::Display for TopologyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TopologyViolation::VertexHalfedgeInvalid(vh) => {
+                write!(f, "Vertex {} has an invalid halfedge", vh)
+            }
+            TopologyViolation::VertexHalfedgeNotOutgoing(vh) => {
+                write!(f, "Vertex {} has a halfedge that is not outgoing", vh)
+            }
+            TopologyViolation::VertexHalfedgeNotBoundary(vh) => {
+                write!(f, "Boundary vertex {} has a non-boundary halfedge", vh)
+            }
+            TopologyViolation::VertexCycleOpen(vh) => {
+                write!(f, "Halfedges around vertex {} do not form a cycle", vh)
+            }
+            TopologyViolation::HalfedgeVertexInvalid(hh) => {
+                write!(f, "Halfedge {} has an invalid vertex", hh)
+            }
+            TopologyViolation::HalfedgeNextInvalid(hh) => {
+                write!(
+                    f,
+                    "Halfedge {} has an invalid next or previous halfedge",
+                    hh
+                )
+            }
+            TopologyViolation::HalfedgeNextPrevMismatch(hh) => {
+                write!(
+                    f,
+                    "Halfedge {} has inconsistent next and previous halfedges",
+                    hh
+                )
+            }
+            TopologyViolation::HalfedgeNotConnected(hh) => {
+                write!(f, "Halfedge {} is not connected to its next halfedge", hh)
+            }
+            TopologyViolation::HalfedgeFaceInvalid(hh) => {
+                write!(f, "Halfedge {} has an invalid face", hh)
+            }
+            TopologyViolation::HalfedgeFaceMismatch(hh) => {
+                write!(f, "Halfedge {} and its next halfedge differ in face", hh)
+            }
+            TopologyViolation::DegenerateEdge(eh) => write!(f, "Edge {} is degenerate", eh),
+            TopologyViolation::FaceHalfedgeInvalid(fh) => {
+                write!(f, "Face {} has an invalid halfedge", fh)
+            }
+            TopologyViolation::FaceCycleOpen(fh) => {
+                write!(f, "Halfedges of face {} do not form a cycle", fh)
+            }
+            TopologyViolation::DegenerateFace(fh) => {
+                write!(f, "Face {} has fewer than 3 halfedges", fh)
+            }
+            TopologyViolation::PropertyLength {
+                item,
+                ref name,
+                len,
+                expected,
+            } => write!(
+                f,
+                "The {} property \"{}\" has length {} instead of {}",
+                item, name, len, expected
+            ),
+        }
+    }
+}
+
+impl Mesh {
+    /// Walks all vertices, halfedges, edges, and faces that are not DELETED, and returns all
+    /// violations of the mesh invariants found. The mesh is consistent if the result is empty.
+    ///
+    /// Unlike the mesh accessors, this never panics on out-of-bounds handles, so it can be used
+    /// to diagnose a mesh after custom edit operations. Items beyond the end of a status property
+    /// that is too short, which is reported as a `PropertyLength` violation, count as not DELETED.
+    pub fn check_topology(&self) -> Vec<TopologyViolation> {
+        let mut violations = Vec::new();
+        self.check_vertices(&mut violations);
+        self.check_halfedges(&mut violations);
+        self.check_faces(&mut violations);
+        self.check_props(&mut violations);
+        violations
+    }
+
+    /// Like `Mesh::is_deleted()`, but false if `h` is out of bounds of the status property.
+    fn is_deleted_checked<H: MeshItemHandle>(&self, h: H) -> bool {
+        H::status_prop(self)
+            .and_then(|prop| prop.storage.get(h.index_us()))
+            .is_some_and(|status| status.deleted())
+    }
+
+    /// Whether `vh` is in bounds and not DELETED.
+    fn is_live_vertex(&self, vh: VertexHandle) -> bool {
+        self.vertices().is_valid(vh) && !self.is_deleted_checked(vh)
+    }
+
+    /// Whether `hh` is in bounds and its edge is not DELETED.
+    fn is_live_halfedge(&self, hh: HalfedgeHandle) -> bool {
+        self.halfedges().is_valid(hh) && !self.is_deleted_checked(self.edge(hh))
+    }
+
+    /// The halfedges whose edges are not DELETED, including HIDDEN ones.
+    fn live_halfedges(&self) -> impl Iterator<Item = HalfedgeHandle> + '_ {
+        self.halfedges()
+            .iter_all()
+            .filter(move |&hh| !self.is_deleted_checked(self.edge(hh)))
+    }
+
+    /// Whether `fh` is in bounds and not DELETED.
+    fn is_live_face(&self, fh: FaceHandle) -> bool {
+        self.faces().is_valid(fh) && !self.is_deleted_checked(fh)
+    }
+
+    fn check_vertices(&self, violations: &mut Vec<TopologyViolation>) {
+        let mut has_edges = vec![false; self.vertices().len() as usize];
+        for hh in self.live_halfedges() {
+            let vh = self.to_vertex(hh);
+            if self.vertices().is_valid(vh) {
+                has_edges[vh.index_us()] = true;
+            }
+        }
+
+        let max_valence = self.halfedges().len();
+        for vh in self
+            .vertices()
+            .iter_all()
+            .filter(|&vh| !self.is_deleted_checked(vh))
+        {
+            let hh = self.vertex_halfedge(vh);
+            if !hh.is_valid() {
+                if has_edges[vh.index_us()] {
+                    violations.push(TopologyViolation::VertexHalfedgeInvalid(vh));
+                }
+                continue;
+            }
+            if !self.is_live_halfedge(hh) {
+                violations.push(TopologyViolation::VertexHalfedgeInvalid(vh));
+                continue;
+            }
+            if self.from_vertex(hh) != vh {
+                violations.push(TopologyViolation::VertexHalfedgeNotOutgoing(vh));
+                continue;
+            }
+
+            // Rotate through the outgoing halfedges until returning to `hh`.
+            let mut any_boundary = false;
+            let mut closed = false;
+            let mut it = hh;
+            for _ in 0..max_valence {
+                any_boundary |= self.is_boundary_halfedge(it);
+                let opp = self.opposite_halfedge(it);
+                it = self.next_halfedge(opp);
+                if !self.is_live_halfedge(it) || self.from_vertex(it) != vh {
+                    break;
+                }
+                if it == hh {
+                    closed = true;
+                    break;
+                }
+            }
+            if !closed {
+                violations.push(TopologyViolation::VertexCycleOpen(vh));
+            } else if any_boundary && !self.is_boundary_halfedge(hh) {
+                violations.push(TopologyViolation::VertexHalfedgeNotBoundary(vh));
+            }
+        }
+    }
+
+    fn check_halfedges(&self, violations: &mut Vec<TopologyViolation>) {
+        for eh in self
+            .edges()
+            .iter_all()
+            .filter(|&eh| !self.is_deleted_checked(eh))
+        {
+            let (h0, h1) = (self.edge_halfedge(eh, 0), self.edge_halfedge(eh, 1));
+            if self.to_vertex(h0) == self.to_vertex(h1) {
+                violations.push(TopologyViolation::DegenerateEdge(eh));
+            }
+        }
+
+        for hh in self.live_halfedges() {
+            if !self.is_live_vertex(self.to_vertex(hh)) {
+                violations.push(TopologyViolation::HalfedgeVertexInvalid(hh));
+            }
+            let fh = self.face(hh);
+            if fh.is_valid() && !self.is_live_face(fh) {
+                violations.push(TopologyViolation::HalfedgeFaceInvalid(hh));
+            }
+
+            let (next, prev) = (self.next_halfedge(hh), self.prev_halfedge(hh));
+            if !self.is_live_halfedge(next) || !self.is_live_halfedge(prev) {
+                violations.push(TopologyViolation::HalfedgeNextInvalid(hh));
+                continue;
+            }
+            if self.prev_halfedge(next) != hh || self.next_halfedge(prev) != hh {
+                violations.push(TopologyViolation::HalfedgeNextPrevMismatch(hh));
+            }
+            if self.from_vertex(next) != self.to_vertex(hh) {
+                violations.push(TopologyViolation::HalfedgeNotConnected(hh));
+            }
+            if self.face(next) != fh {
+                violations.push(TopologyViolation::HalfedgeFaceMismatch(hh));
+            }
+        }
+    }
+
+    fn check_faces(&self, violations: &mut Vec<TopologyViolation>) {
+        let max_valence = self.halfedges().len();
+        for fh in self
+            .faces()
+            .iter_all()
+            .filter(|&fh| !self.is_deleted_checked(fh))
+        {
+            let hh = self.face_halfedge(fh);
+            if !self.is_live_halfedge(hh) || self.face(hh) != fh {
+                violations.push(TopologyViolation::FaceHalfedgeInvalid(fh));
+                continue;
+            }
+
+            // Follow the next halfedges until returning to `hh`.
+            let mut valence = None;
+            let mut it = hh;
+            for n in 1..=max_valence {
+                it = self.next_halfedge(it);
+                if !self.is_live_halfedge(it) || self.face(it) != fh {
+                    break;
+                }
+                if it == hh {
+                    valence = Some(n);
+                    break;
+                }
+            }
+            match valence {
+                None => violations.push(TopologyViolation::FaceCycleOpen(fh)),
+                Some(n) if n < 3 => violations.push(TopologyViolation::DegenerateFace(fh)),
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn check_props(&self, violations: &mut Vec<TopologyViolation>) {
+        fn check<H: ItemHandle>(
+            props: &PropertyContainer<H>,
+            item: &'static str,
+            expected: Size,
+            violations: &mut Vec<TopologyViolation>,
+        ) {
+            let expected = expected as usize;
+            for prop in props.iter() {
+                if prop.len() != expected {
+                    violations.push(TopologyViolation::PropertyLength {
+                        item,
+                        name: prop.name().to_owned(),
+                        len: prop.len(),
+                        expected,
+                    });
+                }
+            }
+        }
+        check(&self.v_props, "vertex", self.vertices().len(), violations);
+        check(
+            &self.h_props,
+            "halfedge",
+            self.halfedges().len(),
+            violations,
+        );
+        check(&self.e_props, "edge", self.edges().len(), violations);
+        check(&self.f_props, "face", self.faces().len(), violations);
+        check(&self.m_props, "mesh", 1, violations);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TopologyViolation;
+    use crate::mesh::item_handle::{FaceHandle, HalfedgeHandle, VertexHandle};
+    use crate::mesh::test_util::{fan, grid, request_status};
+    use crate::property::Handle;
+
+    #[test]
+    fn consistent() {
+        let (mut mesh, v, f) = fan(5);
+        assert_eq!(mesh.check_topology(), vec![]);
+        request_status(&mut mesh);
+        mesh.delete_face(f[0], false).unwrap();
+        mesh.delete_vertex(v[3], true).unwrap();
+        assert_eq!(mesh.check_topology(), vec![]);
+        let (mesh, _) = grid(3);
+        assert_eq!(mesh.check_topology(), vec![]);
+    }
+
+    #[test]
+    fn broken_links() {
+        let (mut mesh, v, f) = fan(4);
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        let next = mesh.next_halfedge(hh);
+        let other = mesh.find_halfedge(v[0], v[3]);
+        mesh.halfedge_item_mut(hh).hnext = other;
+        let violations = mesh.check_topology();
+        assert!(violations.contains(&TopologyViolation::HalfedgeNextPrevMismatch(hh)));
+        assert!(violations.contains(&TopologyViolation::HalfedgeNotConnected(hh)));
+        assert!(violations.contains(&TopologyViolation::HalfedgeFaceMismatch(hh)));
+        mesh.halfedge_item_mut(hh).hnext = next;
+        assert_eq!(mesh.check_topology(), vec![]);
+
+        mesh.halfedge_item_mut(hh).hnext = HalfedgeHandle::from_index(100);
+        assert!(mesh
+            .check_topology()
+            .contains(&TopologyViolation::HalfedgeNextInvalid(hh)));
+        mesh.halfedge_item_mut(hh).hnext = next;
+
+        // Anchor the boundary vertex v1 at an interior halfedge.
+        let boundary_hh = mesh.vertex_halfedge(v[1]);
+        mesh.set_vertex_halfedge(v[1], mesh.find_halfedge(v[1], v[0]));
+        assert_eq!(
+            mesh.check_topology(),
+            vec![TopologyViolation::VertexHalfedgeNotBoundary(v[1])]
+        );
+        mesh.set_vertex_halfedge(v[1], mesh.opposite_halfedge(boundary_hh));
+        assert_eq!(
+            mesh.check_topology(),
+            vec![TopologyViolation::VertexHalfedgeNotOutgoing(v[1])]
+        );
+        mesh.set_vertex_halfedge(v[1], HalfedgeHandle::new());
+        assert_eq!(
+            mesh.check_topology(),
+            vec![TopologyViolation::VertexHalfedgeInvalid(v[1])]
+        );
+        mesh.set_vertex_halfedge(v[1], boundary_hh);
+
+        mesh.set_face_halfedge(f[0], mesh.find_halfedge(v[0], v[3]));
+        assert_eq!(
+            mesh.check_topology(),
+            vec![TopologyViolation::FaceHalfedgeInvalid(f[0])]
+        );
+        mesh.set_face_halfedge(f[0], hh);
+
+        mesh.set_to_vertex(hh, v[0]);
+        let violations = mesh.check_topology();
+        assert!(violations.contains(&TopologyViolation::DegenerateEdge(mesh.edge(hh))));
+        mesh.set_to_vertex(hh, VertexHandle::from_index(100));
+        assert!(mesh
+            .check_topology()
+            .contains(&TopologyViolation::HalfedgeVertexInvalid(hh)));
+        mesh.set_to_vertex(hh, v[1]);
+
+        mesh.set_face(hh, FaceHandle::from_index(100));
+        assert!(mesh
+            .check_topology()
+            .contains(&TopologyViolation::HalfedgeFaceInvalid(hh)));
+        mesh.set_face(hh, f[0]);
+        assert_eq!(mesh.check_topology(), vec![]);
+    }
+
+    #[test]
+    fn property_lengths() {
+        let (mut mesh, _, _) = fan(3);
+        let prop = mesh.e_props_mut().add::<u32>(Some("weight".into()));
+        mesh.e_props.get_mut(prop).unwrap().storage.push(0);
+        assert_eq!(
+            mesh.check_topology(),
+            vec![TopologyViolation::PropertyLength {
+                item: "edge",
+                name: "weight".into(),
+                len: 7,
+                expected: 6,
+            }]
+        );
+    }
+
+    #[test]
+    fn short_status() {
+        // Status lists are only reported, even with DELETED items beyond their ends.
+        let (mut mesh, v, _) = fan(3);
+        request_status(&mut mesh);
+        mesh.delete_vertex(v[3], false).unwrap();
+        mesh.get_vertex_status_mut().unwrap().storage.truncate(1);
+        mesh.get_edge_status_mut().unwrap().storage.truncate(1);
+        mesh.get_face_status_mut().unwrap().storage.truncate(1);
+        let violations = mesh.check_topology();
+        for &(item, expected) in &[("vertex", 4), ("edge", 6), ("face", 3)] {
+            assert!(violations.contains(&TopologyViolation::PropertyLength {
+                item,
+                name: format!("{}:status", &item[..1]),
+                len: 1,
+                expected,
+            }));
+        }
+    }
+}
@@ -0,0 +1,586 @@
+//! Halfedge collapse and its inverse, vertex split, in the spirit of OpenMesh's
+//! `PolyConnectivity::collapse()` and `TriConnectivity::vertex_split()`.
+use crate::geometry::vector::Vec3;
+use crate::mesh::item_handle::{HalfedgeHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+impl Mesh {
+    /// The vertex opposite to `hh` in its face if the face is a triangle.
+    fn triangle_tip(&self, hh: HalfedgeHandle) -> Option<VertexHandle> {
+        let fh = self.face(hh);
+        if fh.is_valid() && self.face_valence(fh) == 3 {
+            Some(self.to_vertex(self.next_halfedge(hh)))
+        } else {
+            None
+        }
+    }
+
+    /// Whether collapsing the triangle containing `hh` onto `hh`'s edge would break the mesh:
+    /// either its two other edges are both on the boundary, or they are shared with the same
+    /// non-triangular face on the other side.
+    fn is_triangle_collapse_degenerate(&self, hh: HalfedgeHandle) -> bool {
+        let h1 = self.next_halfedge(hh);
+        let h2 = self.next_halfedge(h1);
+        let o1 = self.opposite_halfedge(h1);
+        let o2 = self.opposite_halfedge(h2);
+        if self.is_boundary_halfedge(o1) && self.is_boundary_halfedge(o2) {
+            return true;
+        }
+        let fh = self.face(o1);
+        fh == self.face(o2) && fh.is_valid() && self.face_valence(fh) != 3
+    }
+
+    /// Whether the halfedge `v0v1` can be collapsed by `collapse()` without breaking the mesh's
+    /// topology. This fails if
+    ///
+    /// - the edge or either of its vertices is DELETED, or the edge or `v0` is LOCKED,
+    /// - a triangle incident to the edge has both its other edges on the boundary,
+    /// - both vertices are on the boundary, but the edge is not, or
+    /// - the one-rings of `v0` and `v1` share any vertices other than the tips of the triangles
+    ///   incident to the edge (the link condition).
+    pub fn is_collapse_ok(&self, v0v1: HalfedgeHandle) -> bool {
+        let v1v0 = self.opposite_halfedge(v0v1);
+        let v0 = self.to_vertex(v1v0);
+        let v1 = self.to_vertex(v0v1);
+        let eh = self.edge(v0v1);
+        if self.is_deleted(eh) || self.is_deleted(v0) || self.is_deleted(v1) {
+            return false;
+        }
+        if self.item_status(eh).locked() || self.item_status(v0).locked() {
+            return false;
+        }
+
+        let vl = self.triangle_tip(v0v1);
+        let vr = self.triangle_tip(v1v0);
+        if vl.is_some() && self.is_triangle_collapse_degenerate(v0v1) {
+            return false;
+        }
+        if vr.is_some() && self.is_triangle_collapse_degenerate(v1v0) {
+            return false;
+        }
+        if vl.is_some() && vl == vr {
+            return false;
+        }
+
+        // An edge between two boundary vertices should be a boundary edge.
+        if self.is_boundary_vertex(v0)
+            && self.is_boundary_vertex(v1)
+            && !self.is_boundary_halfedge(v0v1)
+            && !self.is_boundary_halfedge(v1v0)
+        {
+            return false;
+        }
+
+        // Test the intersection of the one-rings of `v0` and `v1`.
+        let ring1 = self.vv_ccw_iter(v1).collect::<Vec<_>>();
+        self.vv_ccw_iter(v0)
+            .all(|vh| Some(vh) == vl || Some(vh) == vr || !ring1.contains(&vh))
+    }
+
+    /// Collapses the halfedge `v0v1` by moving `v0` onto `v1`. This removes `v0` and the edge,
+    /// and also any triangles incident to the edge along with one of their other edges each.
+    /// The faces next to the removed triangles take over the halfedge properties of the removed
+    /// edges, and `v1` keeps its own properties.
+    ///
+    /// Removed items are marked DELETED, so this fails if the vertex, edge, or face status
+    /// property is missing. The collapse must be checked with `is_collapse_ok()` first.
+    pub fn collapse(&mut self, v0v1: HalfedgeHandle) -> Result<()> {
+        self.check_deletion_status()?;
+        let h1 = self.next_halfedge(v0v1);
+        let o0 = self.opposite_halfedge(v0v1);
+        let o1 = self.next_halfedge(o0);
+
+        self.collapse_edge(v0v1);
+
+        // Remove the loops left by the collapsed triangles.
+        if self.next_halfedge(self.next_halfedge(h1)) == h1 {
+            self.collapse_loop(self.next_halfedge(h1));
+        }
+        if self.next_halfedge(self.next_halfedge(o1)) == o1 {
+            self.collapse_loop(o1);
+        }
+        Ok(())
+    }
+
+    /// Removes the edge of `hh` and its from-vertex by relinking everything to its to-vertex.
+    fn collapse_edge(&mut self, hh: HalfedgeHandle) {
+        let hn = self.next_halfedge(hh);
+        let hp = self.prev_halfedge(hh);
+        let o = self.opposite_halfedge(hh);
+        let on = self.next_halfedge(o);
+        let op = self.prev_halfedge(o);
+        let fh = self.face(hh);
+        let fo = self.face(o);
+        let vh = self.to_vertex(hh);
+        let vo = self.to_vertex(o);
+
+        // halfedge -> vertex
+        let incoming = self.vih_ccw_iter(vo).collect::<Vec<_>>();
+        for ih in incoming {
+            self.set_to_vertex(ih, vh);
+        }
+
+        // halfedge -> halfedge
+        self.set_next_halfedge(hp, hn);
+        self.set_next_halfedge(op, on);
+
+        // face -> halfedge
+        if fh.is_valid() {
+            self.set_face_halfedge(fh, hn);
+        }
+        if fo.is_valid() {
+            self.set_face_halfedge(fo, on);
+        }
+
+        // vertex -> halfedge
+        if self.vertex_halfedge(vh) == o {
+            self.set_vertex_halfedge(vh, hn);
+        }
+        self.adjust_outgoing_halfedge(vh);
+        self.set_vertex_halfedge(vo, HalfedgeHandle::new());
+
+        self.set_deleted(self.edge(hh));
+        self.set_deleted(vo);
+        self.set_deleted(hh);
+        self.set_deleted(o);
+    }
+
+    /// Removes the loop of two halfedges starting at `hh` along with its face (if any) and the
+    /// edge of `hh`. The other halfedge of the loop takes the place of the halfedge opposite to
+    /// `hh`, including its properties.
+    fn collapse_loop(&mut self, hh: HalfedgeHandle) {
+        let h0 = hh;
+        let h1 = self.next_halfedge(h0);
+        let o0 = self.opposite_halfedge(h0);
+        let o1 = self.opposite_halfedge(h1);
+        let v0 = self.to_vertex(h0);
+        let v1 = self.to_vertex(h1);
+        let fh = self.face(h0);
+        let fo = self.face(o0);
+        debug_assert!(self.next_halfedge(h1) == h0 && h1 != o0);
+
+        // halfedge -> halfedge
+        self.set_next_halfedge(h1, self.next_halfedge(o0));
+        self.set_next_halfedge(self.prev_halfedge(o0), h1);
+
+        // halfedge -> face
+        self.set_face(h1, fo);
+        self.h_props.copy_all(o0, h1);
+
+        // vertex -> halfedge
+        self.set_vertex_halfedge(v0, h1);
+        self.adjust_outgoing_halfedge(v0);
+        self.set_vertex_halfedge(v1, o1);
+        self.adjust_outgoing_halfedge(v1);
+
+        // face -> halfedge
+        if fo.is_valid() && self.face_halfedge(fo) == o0 {
+            self.set_face_halfedge(fo, h1);
+        }
+
+        if fh.is_valid() {
+            self.set_face_halfedge(fh, HalfedgeHandle::new());
+            self.set_deleted(fh);
+        }
+        self.set_deleted(self.edge(h0));
+        self.set_deleted(h0);
+        self.set_deleted(o0);
+    }
+
+    /// Splits the vertex `v1` by adding a new vertex `v0` at `v0_pos` and an edge from `v0` to
+    /// `v1`, and returns the halfedge from `v0` to `v1`. This undoes `collapse()` on that
+    /// halfedge: `vl` and `vr` are the tips of the triangles to the left and right of the
+    /// halfedge, and the edges of `v1` from `vl` clockwise to `vr` (exclusive) are moved to `v0`.
+    ///
+    /// On a boundary, either `vl` or `vr` may be invalid, in which case the new edge is a
+    /// boundary edge on that side.
+    ///
+    /// Fails without changing the mesh if `v1` is invalid, the halfedges from `v1` to `vl` or from
+    /// `vr` to `v1` do not exist, or `v1` is not on the boundary when `vl` or `vr` is invalid. If
+    /// both `vl` and `vr` are invalid, fails with `TopologyError::InvalidVertex(vl)`.
+    pub fn vertex_split(
+        &mut self,
+        v0_pos: Vec3<f64>,
+        v1: VertexHandle,
+        vl: VertexHandle,
+        vr: VertexHandle,
+    ) -> Result<HalfedgeHandle> {
+        if !self.vertices().is_valid(v1) {
+            return Err(TopologyError::InvalidVertex(v1));
+        }
+        if !vl.is_valid() && !vr.is_valid() {
+            return Err(TopologyError::InvalidVertex(vl));
+        }
+        let v1vl = if vl.is_valid() {
+            self.find_halfedge(v1, vl)
+        } else {
+            HalfedgeHandle::new()
+        };
+        let vrv1 = if vr.is_valid() {
+            self.find_halfedge(vr, v1)
+        } else {
+            HalfedgeHandle::new()
+        };
+        if vl.is_valid() && !v1vl.is_valid() {
+            return Err(TopologyError::MissingHalfedge(v1, vl));
+        }
+        if vr.is_valid() && !vrv1.is_valid() {
+            return Err(TopologyError::MissingHalfedge(vr, v1));
+        }
+        if (!vl.is_valid() || !vr.is_valid()) && !self.is_boundary_vertex(v1) {
+            return Err(TopologyError::ComplexVertex(v1));
+        }
+
+        let v0 = self.add_vertex(v0_pos);
+        // Build the triangles' loops from the halfedges v1->vl and vr->v1.
+        let vlv1 = if vl.is_valid() {
+            self.insert_loop(v1vl)
+        } else {
+            self.prev_halfedge(self.vertex_halfedge(v1))
+        };
+        let vrv1 = if vr.is_valid() {
+            self.insert_loop(vrv1);
+            vrv1
+        } else {
+            self.prev_halfedge(self.vertex_halfedge(v1))
+        };
+        Ok(self.insert_vertex_edge(v0, vlv1, vrv1))
+    }
+
+    /// Doubles the edge of `hh` into a loop of two halfedges, `hh` and a new one, inside a new
+    /// face. Returns the new halfedge, which points to the from-vertex of `hh`.
+    fn insert_loop(&mut self, hh: HalfedgeHandle) -> HalfedgeHandle {
+        let h0 = hh;
+        let o0 = self.opposite_halfedge(h0);
+        let v0 = self.to_vertex(o0);
+        let v1 = self.to_vertex(h0);
+
+        let h1 = self.new_edge(v1, v0);
+        let o1 = self.opposite_halfedge(h1);
+        let f0 = self.face(h0);
+        let f1 = self.new_face();
+
+        // halfedge -> halfedge
+        self.set_next_halfedge(self.prev_halfedge(h0), o1);
+        self.set_next_halfedge(o1, self.next_halfedge(h0));
+        self.set_next_halfedge(h1, h0);
+        self.set_next_halfedge(h0, h1);
+
+        // halfedge -> face
+        self.set_face(o1, f0);
+        self.set_face(h0, f1);
+        self.set_face(h1, f1);
+
+        // face -> halfedge
+        self.set_face_halfedge(f1, h0);
+        if f0.is_valid() {
+            self.set_face_halfedge(f0, o1);
+        }
+
+        // vertex -> halfedge
+        self.adjust_outgoing_halfedge(v0);
+        self.adjust_outgoing_halfedge(v1);
+        h1
+    }
+
+    /// Adds an edge from the isolated vertex `vh` to the common to-vertex of `h0` and `h1`, and
+    /// moves the edges between `h0` and `h1` (clockwise from `h0`) to `vh`. Returns the halfedge
+    /// from `vh`.
+    fn insert_vertex_edge(
+        &mut self,
+        vh: VertexHandle,
+        h0: HalfedgeHandle,
+        h1: HalfedgeHandle,
+    ) -> HalfedgeHandle {
+        let v0 = vh;
+        let v1 = self.to_vertex(h0);
+        debug_assert!(v1 == self.to_vertex(h1));
+
+        let v0v1 = self.new_edge(v0, v1);
+        let v1v0 = self.opposite_halfedge(v0v1);
+
+        // vertex -> halfedge
+        self.set_vertex_halfedge(v0, v0v1);
+        self.set_vertex_halfedge(v1, v1v0);
+
+        // halfedge -> halfedge
+        self.set_next_halfedge(v0v1, self.next_halfedge(h0));
+        self.set_next_halfedge(h0, v0v1);
+        self.set_next_halfedge(v1v0, self.next_halfedge(h1));
+        self.set_next_halfedge(h1, v1v0);
+
+        // halfedge -> vertex
+        let incoming = self.vih_ccw_iter(v0).collect::<Vec<_>>();
+        for ih in incoming {
+            self.set_to_vertex(ih, v0);
+        }
+
+        // halfedge -> face
+        let (f0, f1) = (self.face(h0), self.face(h1));
+        self.set_face(v0v1, f0);
+        self.set_face(v1v0, f1);
+
+        // face -> halfedge
+        if f0.is_valid() {
+            self.set_face_halfedge(f0, v0v1);
+        }
+        if f1.is_valid() {
+            self.set_face_halfedge(f1, v1v0);
+        }
+
+        // vertex -> halfedge
+        self.adjust_outgoing_halfedge(v0);
+        self.adjust_outgoing_halfedge(v1);
+        v0v1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{HalfedgeHandle, VertexHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{
+        assert_consistent, fan, grid, mesh_with_vertices, request_status,
+    };
+    use crate::mesh::Mesh;
+    use crate::property::{Handle, Index};
+
+    #[test]
+    fn collapse_interior_vertex() {
+        let (mut mesh, v, _) = fan(6);
+        request_status(&mut mesh);
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        assert!(mesh.is_collapse_ok(hh));
+        mesh.collapse(hh).unwrap();
+        assert_consistent(&mesh);
+        assert!(mesh.is_deleted(v[0]));
+        assert_eq!(mesh.vertices().iter().count(), 6);
+        assert_eq!(mesh.edges().iter().count(), 9);
+        assert_eq!(mesh.faces().iter().count(), 4);
+        // `v[1]` is on the boundary, so circulation starts there.
+        assert_eq!(
+            mesh.vv_ccw_iter(v[1]).collect::<Vec<_>>(),
+            vec![v[6], v[2], v[3], v[4], v[5]]
+        );
+    }
+
+    #[test]
+    fn collapse_boundary_edge() {
+        let (mut mesh, v, _) = fan(3);
+        request_status(&mut mesh);
+        mesh.delete_face(mesh.face(mesh.find_halfedge(v[0], v[1])), false)
+            .unwrap();
+        // Collapse a boundary edge onto a boundary vertex. The triangle (v0, v3, v1) collapses.
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        assert!(mesh.is_boundary(hh));
+        assert!(mesh.is_collapse_ok(hh));
+        mesh.collapse(hh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().iter().count(), 1);
+        let fh = mesh.faces().iter().next().unwrap();
+        let mut fvs = mesh.fv_ccw_iter(fh).collect::<Vec<_>>();
+        fvs.sort_by_key(|vh| vh.index());
+        assert_eq!(fvs, vec![v[1], v[2], v[3]]);
+        assert!(!mesh.is_collapse_ok(mesh.find_halfedge(v[1], v[2])));
+    }
+
+    #[test]
+    fn collapse_polygons() {
+        let (mut mesh, v) = grid(3);
+        request_status(&mut mesh);
+        // Both vertices are interior, and both adjacent quads become triangles.
+        let hh = mesh.find_halfedge(v[5], v[6]);
+        assert!(mesh.is_collapse_ok(hh));
+        let (fl, fr) = (mesh.face(hh), mesh.face(mesh.opposite_halfedge(hh)));
+        mesh.collapse(hh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().iter().count(), 9);
+        assert_eq!(mesh.face_valence(fl), 3);
+        assert_eq!(mesh.face_valence(fr), 3);
+        assert_eq!(mesh.vertex_valence(v[6]), 6);
+        assert_eq!(mesh.fv_ccw_iter(fl).filter(|&vh| vh == v[5]).count(), 0);
+    }
+
+    #[test]
+    fn collapse_merges_halfedge_props() {
+        let (mut mesh, v, _) = fan(6);
+        request_status(&mut mesh);
+        let prop = mesh.h_props_mut().add::<u32>(Some("index".into()));
+        for i in 0..mesh.halfedges().len() {
+            mesh.h_props_mut().get_mut(prop).unwrap()[HalfedgeHandle::from_index(i)] = i;
+        }
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        let h1 = mesh.next_halfedge(hh);
+        let o2 = mesh.opposite_halfedge(mesh.next_halfedge(h1));
+        mesh.collapse(hh).unwrap();
+        // `h1` replaced `o2` in the face (v0, v2, v3), which is now (v1, v2, v3).
+        assert_eq!(mesh.face(h1), mesh.face(mesh.next_halfedge(h1)));
+        assert!(mesh.is_deleted(o2));
+        let values = mesh.h_props().get(prop).unwrap();
+        assert_eq!(values[h1], o2.index() as Index);
+    }
+
+    #[test]
+    fn collapse_not_ok() {
+        // A single triangle.
+        let (mut mesh, v) = mesh_with_vertices(3);
+        request_status(&mut mesh);
+        mesh.add_face(&v).unwrap();
+        assert!(!mesh.is_collapse_ok(mesh.find_halfedge(v[0], v[1])));
+
+        // Interior edge between boundary vertices.
+        let (mut mesh, v) = mesh_with_vertices(4);
+        request_status(&mut mesh);
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        mesh.add_face(&[v[0], v[2], v[3]]).unwrap();
+        assert!(!mesh.is_collapse_ok(mesh.find_halfedge(v[0], v[2])));
+        assert!(mesh.is_collapse_ok(mesh.find_halfedge(v[0], v[1])));
+
+        // Link condition: ring vertices 1 and 3 are connected outside of the fan.
+        let (mut mesh, mut v, _) = fan(4);
+        request_status(&mut mesh);
+        v.push(mesh.add_vertex(Vec3::new(0.0, 0.0, 0.0)));
+        mesh.add_face(&[v[3], v[1], v[5]]).unwrap();
+        assert!(!mesh.is_collapse_ok(mesh.find_halfedge(v[0], v[1])));
+        assert!(mesh.is_collapse_ok(mesh.find_halfedge(v[0], v[2])));
+
+        // Locked vertex or edge.
+        let (mut mesh, v, _) = fan(6);
+        request_status(&mut mesh);
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        mesh.get_vertex_status_mut().unwrap()[v[0]].set_locked(true);
+        assert!(!mesh.is_collapse_ok(hh));
+        assert!(mesh.is_collapse_ok(mesh.opposite_halfedge(hh)));
+        mesh.get_vertex_status_mut().unwrap()[v[0]].set_locked(false);
+        let eh = mesh.edge(hh);
+        mesh.get_edge_status_mut().unwrap()[eh].set_locked(true);
+        assert!(!mesh.is_collapse_ok(hh));
+
+        // Deleted edge.
+        let (mut mesh, v, _) = fan(6);
+        request_status(&mut mesh);
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        mesh.collapse(hh).unwrap();
+        assert!(!mesh.is_collapse_ok(hh));
+    }
+
+    #[test]
+    fn collapse_missing_status() {
+        let mut mesh = Mesh::new();
+        let v = (0..3)
+            .map(|_| mesh.add_vertex(Vec3::new(0.0, 0.0, 0.0)))
+            .collect::<Vec<_>>();
+        mesh.add_face(&v).unwrap();
+        assert_eq!(
+            mesh.collapse(mesh.find_halfedge(v[0], v[1])),
+            Err(TopologyError::MissingStatus("vertex"))
+        );
+    }
+
+    /// Faces not marked DELETED as lists of vertex x-coordinates, each rotated to start at its
+    /// smallest coordinate, sorted. This identifies faces independently of handles.
+    fn face_set(mesh: &Mesh) -> Vec<Vec<i64>> {
+        let mut faces = mesh
+            .faces()
+            .iter()
+            .map(|fh| {
+                let mut xs = mesh
+                    .fv_ccw_iter(fh)
+                    .map(|vh| mesh.point(vh).x as i64)
+                    .collect::<Vec<_>>();
+                let min = (0..xs.len()).min_by_key(|&i| xs[i]).unwrap();
+                xs.rotate_left(min);
+                xs
+            })
+            .collect::<Vec<_>>();
+        faces.sort();
+        faces
+    }
+
+    #[test]
+    fn vertex_split_undoes_collapse() {
+        let (mut mesh, v, _) = fan(5);
+        request_status(&mut mesh);
+        let before = face_set(&mesh);
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        let vl = mesh.to_vertex(mesh.next_halfedge(hh));
+        let vr = mesh.to_vertex(mesh.next_halfedge(mesh.opposite_halfedge(hh)));
+        mesh.collapse(hh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().iter().count(), 3);
+
+        let v0 = mesh
+            .vertex_split(Vec3::new(0.0, 0.0, 0.0), v[1], vl, vr)
+            .unwrap();
+        assert_consistent(&mesh);
+        let v0 = mesh.from_vertex(v0);
+        assert_eq!(mesh.to_vertex(mesh.find_halfedge(v0, v[1])), v[1]);
+        assert_eq!(mesh.vertex_valence(v0), 5);
+        assert_eq!(mesh.vertex_valence(v[1]), 3);
+        assert_eq!(face_set(&mesh), before);
+    }
+
+    #[test]
+    fn vertex_split_undoes_boundary_collapse() {
+        let (mut mesh, v, f) = fan(5);
+        request_status(&mut mesh);
+        mesh.delete_face(f[4], false).unwrap();
+        let before = face_set(&mesh);
+
+        // Left side only: (v0, v1) is on the boundary with face f0 to its left.
+        let hh = mesh.find_halfedge(v[0], v[1]);
+        assert!(mesh.is_boundary(mesh.opposite_halfedge(hh)));
+        let vl = mesh.to_vertex(mesh.next_halfedge(hh));
+        mesh.collapse(hh).unwrap();
+        assert_consistent(&mesh);
+        let hh = mesh
+            .vertex_split(Vec3::new(0.0, 0.0, 0.0), v[1], vl, VertexHandle::new())
+            .unwrap();
+        assert_consistent(&mesh);
+        assert!(mesh.is_boundary(mesh.opposite_halfedge(hh)));
+        assert_eq!(face_set(&mesh), before);
+
+        // Right side only: (v0, v5) is on the boundary with face f3 to its right.
+        let hh = mesh.find_halfedge(mesh.from_vertex(hh), v[5]);
+        assert!(mesh.is_boundary(hh));
+        let vr = mesh.to_vertex(mesh.next_halfedge(mesh.opposite_halfedge(hh)));
+        mesh.collapse(hh).unwrap();
+        assert_consistent(&mesh);
+        let hh = mesh
+            .vertex_split(Vec3::new(0.0, 0.0, 0.0), v[5], VertexHandle::new(), vr)
+            .unwrap();
+        assert_consistent(&mesh);
+        assert!(mesh.is_boundary(hh));
+        assert_eq!(face_set(&mesh), before);
+    }
+
+    #[test]
+    fn vertex_split_fails() {
+        let (mut mesh, v, _) = fan(4);
+        let pos = Vec3::new(0.0, 0.0, 0.0);
+        assert_eq!(
+            mesh.vertex_split(pos, v[1], v[2], v[3]),
+            Err(TopologyError::MissingHalfedge(v[3], v[1])),
+        );
+        assert_eq!(
+            mesh.vertex_split(pos, v[1], v[3], v[0]),
+            Err(TopologyError::MissingHalfedge(v[1], v[3])),
+        );
+        assert_eq!(
+            mesh.vertex_split(pos, v[0], v[1], VertexHandle::new()),
+            Err(TopologyError::ComplexVertex(v[0])),
+        );
+        assert_eq!(
+            mesh.vertex_split(pos, VertexHandle::from_index(9), v[1], v[2]),
+            Err(TopologyError::InvalidVertex(VertexHandle::from_index(9))),
+        );
+        assert_eq!(
+            mesh.vertex_split(pos, v[1], VertexHandle::new(), VertexHandle::new()),
+            Err(TopologyError::InvalidVertex(VertexHandle::new())),
+        );
+        assert_eq!(mesh.vertices().len(), 5);
+    }
+}
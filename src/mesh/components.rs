@@ -0,0 +1,207 @@
+//! Connected components, i.e. the maximal sets of items connected by edges.
+use crate::mesh::item_handle::{FaceHandle, MeshItemHandle, VertexHandle};
+use crate::mesh::Mesh;
+use crate::property::{FPropHandle, Handle, Index, VPropHandle};
+
+impl Mesh {
+    /// The number of connected components and the component of each vertex, which is
+    /// `u32::MAX` for DELETED vertices. Components are numbered in the order of their first
+    /// vertex.
+    pub(crate) fn vertex_components(&self) -> (usize, Vec<u32>) {
+        let mut labels = vec![u32::MAX; self.vertices.len()];
+        let mut count = 0;
+        let mut stack = Vec::new();
+        for start in self.vertices().iter_all() {
+            if labels[start.index_us()] != u32::MAX || self.is_deleted(start) {
+                continue;
+            }
+            labels[start.index_us()] = count;
+            stack.push(start);
+            while let Some(vh) = stack.pop() {
+                for neighbor in self.vv_ccw_iter(vh) {
+                    if labels[neighbor.index_us()] == u32::MAX {
+                        labels[neighbor.index_us()] = count;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            count += 1;
+        }
+        (count as usize, labels)
+    }
+
+    /// Labels each vertex and face by its connected component, where faces are connected
+    /// through their edges and vertices, and isolated vertices are components of their own.
+    /// Returns the number of components and the face labels, numbered from 0 in the order of the
+    /// components' first vertices.
+    ///
+    /// The labels are stored in the `u32` properties "f:component" and "v:component", which are
+    /// added if they do not already exist. DELETED items are labeled `u32::MAX`.
+    pub fn connected_components(&mut self) -> (usize, FPropHandle<u32>) {
+        let (count, v_labels) = self.vertex_components();
+        let f_labels: Vec<u32> = self
+            .faces()
+            .iter_all()
+            .map(|fh| {
+                if self.is_deleted(fh) {
+                    u32::MAX
+                } else {
+                    v_labels[self.to_vertex(self.face_halfedge(fh)).index_us()]
+                }
+            })
+            .collect();
+
+        let name = VertexHandle::with_prefix("component");
+        let mut v_prop: VPropHandle<u32> = self.v_props().handle(&name);
+        if !v_prop.is_valid() {
+            v_prop = self.v_props_mut().add(Some(name));
+        }
+        let list = self.v_props_mut().get_mut(v_prop).unwrap();
+        for (i, label) in v_labels.into_iter().enumerate() {
+            list[VertexHandle::from_index(i as Index)] = label;
+        }
+
+        let name = FaceHandle::with_prefix("component");
+        let mut f_prop: FPropHandle<u32> = self.f_props().handle(&name);
+        if !f_prop.is_valid() {
+            f_prop = self.f_props_mut().add(Some(name));
+        }
+        let list = self.f_props_mut().get_mut(f_prop).unwrap();
+        for (i, label) in f_labels.into_iter().enumerate() {
+            list[FaceHandle::from_index(i as Index)] = label;
+        }
+        (count, f_prop)
+    }
+
+    /// Copies each connected component (see `Mesh::connected_components()`) into a mesh of its
+    /// own, in the order of the component labels. Items keep their relative order, and all
+    /// properties are copied along with them, so property handles of `self` remain valid for
+    /// each component.
+    pub fn split_components(&self) -> Vec<Mesh> {
+        let (count, v_labels) = self.vertex_components();
+        let mut vhs = vec![Vec::new(); count];
+        for vh in self.vertices().iter_all() {
+            if let Some(vhs) = vhs.get_mut(v_labels[vh.index_us()] as usize) {
+                vhs.push(vh);
+            }
+        }
+        let mut fhs = vec![Vec::new(); count];
+        for fh in self.faces().iter_all() {
+            if !self.is_deleted(fh) {
+                let vh = self.to_vertex(self.face_halfedge(fh));
+                fhs[v_labels[vh.index_us()] as usize].push(fh);
+            }
+        }
+        vhs.iter()
+            .zip(&fhs)
+            .map(|(vhs, fhs)| self.submesh(vhs, fhs).0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::VertexHandle;
+    use crate::mesh::test_util::{assert_consistent, fan, grid, request_status};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    /// A grid of 2x2 quads, a closed fan of 3 triangles, and an isolated vertex, with the fan
+    /// shifted along z by 1.
+    fn shells() -> (Mesh, Vec<VertexHandle>) {
+        let (mut mesh, mut v) = grid(2);
+        let (other, _, _) = fan(3);
+        let offset = v.len();
+        for vh in other.vertices().iter() {
+            v.push(mesh.add_vertex(other.point(vh) + Vec3::new(0.0, 0.0, 1.0)));
+        }
+        for fh in other.faces().iter() {
+            let vhs: Vec<_> = other
+                .fv_ccw_iter(fh)
+                .map(|vh| v[offset + vh.index_us()])
+                .collect();
+            mesh.add_face(&vhs).unwrap();
+        }
+        v.push(mesh.add_vertex(Vec3::new(0.0, 0.0, 2.0)));
+        (mesh, v)
+    }
+
+    #[test]
+    fn connected_components() {
+        let (mut mesh, _) = shells();
+        let (count, f_prop) = mesh.connected_components();
+        assert_eq!(count, 3);
+        let f_labels = &mesh.f_props().get(f_prop).unwrap().storage;
+        assert_eq!(f_labels, &vec![0, 0, 0, 0, 1, 1, 1]);
+        let v_prop = mesh.v_props().handle::<u32>("v:component");
+        let v_labels = &mesh.v_props().get(v_prop).unwrap().storage;
+        assert_eq!(v_labels[..9], [0; 9]);
+        assert_eq!(v_labels[9..13], [1; 4]);
+        assert_eq!(v_labels[13], 2);
+
+        // Labeling again reuses the properties.
+        let n_props = mesh.f_props().len();
+        assert_eq!(mesh.connected_components(), (count, f_prop));
+        assert_eq!(mesh.f_props().len(), n_props);
+    }
+
+    #[test]
+    fn deleted_items() {
+        // Deleting the middle vertex of the fan leaves its ring of 3 isolated vertices.
+        let (mut mesh, v) = shells();
+        request_status(&mut mesh);
+        mesh.delete_vertex(v[9], false).unwrap();
+        let (count, f_prop) = mesh.connected_components();
+        assert_eq!(count, 5);
+        let f_labels = &mesh.f_props().get(f_prop).unwrap().storage;
+        assert_eq!(f_labels, &vec![0, 0, 0, 0, u32::MAX, u32::MAX, u32::MAX]);
+        assert_eq!(mesh.split_components().len(), 5);
+    }
+
+    #[test]
+    fn split_components() {
+        let (mut mesh, _) = shells();
+        let prop = mesh.v_props_mut().add::<u32>(Some("index".into()));
+        for (i, value) in mesh
+            .v_props_mut()
+            .get_mut(prop)
+            .unwrap()
+            .storage
+            .iter_mut()
+            .enumerate()
+        {
+            *value = i as u32;
+        }
+        let meshes = mesh.split_components();
+        let counts = meshes
+            .iter()
+            .map(|m| {
+                (
+                    m.vertices().len(),
+                    m.edges().len(),
+                    m.faces().len(),
+                    m.boundary_loops().len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![(9, 12, 4, 1), (4, 6, 3, 1), (1, 0, 0, 0)]);
+        for m in &meshes {
+            assert_consistent(m);
+        }
+        let indices = &meshes[1].v_props().get(prop).unwrap().storage;
+        assert_eq!(indices, &vec![9, 10, 11, 12]);
+        for vh in meshes[1].vertices().iter() {
+            assert_eq!(
+                meshes[1].point(vh),
+                mesh.point(VertexHandle::from_index(indices[vh.index_us()]))
+            );
+        }
+        let fvs = meshes[1]
+            .faces()
+            .iter()
+            .map(|fh| meshes[1].fv_ccw_iter(fh).count())
+            .collect::<Vec<_>>();
+        assert_eq!(fvs, vec![3, 3, 3]);
+    }
+}
@@ -0,0 +1,325 @@
+//! Polygonal mesh construction, in the spirit of OpenMesh's `PolyConnectivity`.
+use crate::mesh::item_handle::{FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+/// Per-edge scratch data for `Mesh::add_face`.
+#[derive(Clone, Copy, Default)]
+struct AddFaceEdge {
+    /// Halfedge from the `i`th to the `(i+1)`th vertex.
+    hh: HalfedgeHandle,
+    /// Whether the edge needs to be created.
+    is_new: bool,
+    /// Whether the outgoing halfedge of the `i`th vertex needs adjusting.
+    needs_adjust: bool,
+}
+
+impl Mesh {
+    /// Adds a face bounded by the given vertices in counter-clockwise order, and returns its
+    /// handle.
+    ///
+    /// Existing boundary halfedges between consecutive vertices are reused, and new edges are
+    /// created for the rest. Halfedge loops around the vertices are re-linked as needed.
+    ///
+    /// On error, the mesh is left unchanged. This fails if
+    ///
+    /// - there are fewer than 3 distinct vertices, or any vertex handle is invalid,
+    /// - any vertex is not on the boundary (complex vertex),
+    /// - an edge already has faces on both sides (complex edge),
+    /// - an edge has a face on the side of the new face (flipped orientation), or
+    /// - the existing faces around a vertex leave no gap for the new face.
+    pub fn add_face(&mut self, vhs: &[VertexHandle]) -> Result<FaceHandle> {
+        let n = vhs.len();
+        if n < 3 {
+            return Err(TopologyError::DegenerateFace);
+        }
+        for (i, &vh) in vhs.iter().enumerate() {
+            if !self.vertices().is_valid(vh) {
+                return Err(TopologyError::InvalidVertex(vh));
+            }
+            if vhs[..i].contains(&vh) {
+                return Err(TopologyError::DegenerateFace);
+            }
+        }
+
+        // Test for topological errors.
+        let mut edge_data = vec![AddFaceEdge::default(); n];
+        for i in 0..n {
+            let (v0, v1) = (vhs[i], vhs[(i + 1) % n]);
+            if !self.is_boundary_vertex(v0) {
+                return Err(TopologyError::ComplexVertex(v0));
+            }
+            let hh = self.find_halfedge(v0, v1);
+            edge_data[i].hh = hh;
+            edge_data[i].is_new = !hh.is_valid();
+            if !edge_data[i].is_new && !self.is_boundary_halfedge(hh) {
+                let opp = self.opposite_halfedge(hh);
+                return Err(if self.is_boundary_halfedge(opp) {
+                    TopologyError::FlippedOrientation(v0, v1)
+                } else {
+                    TopologyError::ComplexEdge(v0, v1)
+                });
+            }
+        }
+
+        // `hnext` links to be set once all the checks have passed.
+        let mut next_cache: Vec<(HalfedgeHandle, HalfedgeHandle)> = Vec::with_capacity(6 * n);
+
+        // Re-link patches if necessary.
+        for i in 0..n {
+            let ii = (i + 1) % n;
+            if edge_data[i].is_new || edge_data[ii].is_new {
+                continue;
+            }
+            let inner_prev = edge_data[i].hh;
+            let inner_next = edge_data[ii].hh;
+            if self.next_halfedge(inner_prev) == inner_next {
+                continue;
+            }
+
+            // The patch between `inner_prev` and `inner_next` has to be moved into a free gap
+            // between `boundary_prev` and `boundary_next` around the vertex.
+            let outer_prev = self.opposite_halfedge(inner_next);
+            let mut boundary_prev = outer_prev;
+            loop {
+                boundary_prev = self.opposite_halfedge(self.next_halfedge(boundary_prev));
+                if self.is_boundary_halfedge(boundary_prev) {
+                    break;
+                }
+            }
+            if boundary_prev == inner_prev {
+                return Err(TopologyError::PatchRelinkingFailed(vhs[ii]));
+            }
+            let boundary_next = self.next_halfedge(boundary_prev);
+            debug_assert!(self.is_boundary_halfedge(boundary_next));
+
+            let patch_start = self.next_halfedge(inner_prev);
+            let patch_end = self.prev_halfedge(inner_next);
+            next_cache.push((boundary_prev, patch_start));
+            next_cache.push((patch_end, boundary_next));
+            next_cache.push((inner_prev, inner_next));
+        }
+
+        // All checks passed. Create the missing edges.
+        for i in 0..n {
+            if edge_data[i].is_new {
+                edge_data[i].hh = self.new_edge(vhs[i], vhs[(i + 1) % n]);
+            }
+        }
+
+        // Create the face.
+        let fh = self.new_face();
+        self.set_face_halfedge(fh, edge_data[n - 1].hh);
+
+        // Set up the halfedges.
+        for i in 0..n {
+            let ii = (i + 1) % n;
+            let vh = vhs[ii];
+            let inner_prev = edge_data[i].hh;
+            let inner_next = edge_data[ii].hh;
+
+            match (edge_data[i].is_new, edge_data[ii].is_new) {
+                (false, false) => {
+                    edge_data[ii].needs_adjust = self.vertex_halfedge(vh) == inner_next;
+                }
+                (prev_is_new, next_is_new) => {
+                    let outer_prev = self.opposite_halfedge(inner_next);
+                    let outer_next = self.opposite_halfedge(inner_prev);
+                    match (prev_is_new, next_is_new) {
+                        (true, false) => {
+                            let boundary_prev = self.prev_halfedge(inner_next);
+                            next_cache.push((boundary_prev, outer_next));
+                            self.set_vertex_halfedge(vh, outer_next);
+                        }
+                        (false, true) => {
+                            let boundary_next = self.next_halfedge(inner_prev);
+                            next_cache.push((outer_prev, boundary_next));
+                            self.set_vertex_halfedge(vh, boundary_next);
+                        }
+                        _ => {
+                            let boundary_next = self.vertex_halfedge(vh);
+                            if !boundary_next.is_valid() {
+                                self.set_vertex_halfedge(vh, outer_next);
+                                next_cache.push((outer_prev, outer_next));
+                            } else {
+                                let boundary_prev = self.prev_halfedge(boundary_next);
+                                next_cache.push((boundary_prev, outer_next));
+                                next_cache.push((outer_prev, boundary_next));
+                            }
+                        }
+                    }
+                    next_cache.push((inner_prev, inner_next));
+                }
+            }
+
+            self.set_face(inner_prev, fh);
+        }
+
+        for (hh, hnext) in next_cache {
+            self.set_next_halfedge(hh, hnext);
+        }
+
+        for (i, data) in edge_data.iter().enumerate() {
+            if data.needs_adjust {
+                self.adjust_outgoing_halfedge(vhs[i]);
+            }
+        }
+
+        Ok(fh)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mesh::item_handle::{FaceHandle, HalfedgeHandle, VertexHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, mesh_with_vertices};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    /// Halfedges of the face loop starting at `fh`'s halfedge.
+    fn face_loop(mesh: &Mesh, fh: FaceHandle) -> Vec<HalfedgeHandle> {
+        let start = mesh.face_halfedge(fh);
+        let mut hh = start;
+        let mut res = Vec::new();
+        loop {
+            res.push(hh);
+            hh = mesh.next_halfedge(hh);
+            if hh == start || res.len() > mesh.halfedges().len() as usize {
+                break;
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn single_quad() {
+        let (mut mesh, vhs) = mesh_with_vertices(4);
+        let fh = mesh.add_face(&vhs).unwrap();
+        assert_eq!(mesh.faces().len(), 1);
+        assert_eq!(mesh.edges().len(), 4);
+        assert_eq!(mesh.halfedges().len(), 8);
+        assert_consistent(&mesh);
+        let face_vertices = face_loop(&mesh, fh)
+            .into_iter()
+            .map(|hh| mesh.from_vertex(hh))
+            .collect::<Vec<_>>();
+        assert_eq!(face_vertices.len(), 4);
+        let offset = face_vertices.iter().position(|&vh| vh == vhs[0]).unwrap();
+        for (i, &vh) in vhs.iter().enumerate() {
+            assert_eq!(face_vertices[(offset + i) % 4], vh);
+        }
+        for &vh in vhs.iter() {
+            assert!(mesh.is_boundary_vertex(vh));
+        }
+    }
+
+    #[test]
+    fn shared_edge() {
+        let (mut mesh, v) = mesh_with_vertices(4);
+        let f0 = mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        let f1 = mesh.add_face(&[v[0], v[2], v[3]]).unwrap();
+        assert_eq!(mesh.edges().len(), 5);
+        assert_consistent(&mesh);
+        let hh = mesh.find_halfedge(v[2], v[0]);
+        assert_eq!(mesh.face(hh), f0);
+        assert_eq!(mesh.face(mesh.opposite_halfedge(hh)), f1);
+    }
+
+    #[test]
+    fn closed_tetrahedron() {
+        let (mut mesh, v) = mesh_with_vertices(4);
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        mesh.add_face(&[v[0], v[2], v[3]]).unwrap();
+        mesh.add_face(&[v[0], v[3], v[1]]).unwrap();
+        mesh.add_face(&[v[1], v[3], v[2]]).unwrap();
+        assert_eq!(mesh.edges().len(), 6);
+        assert_eq!(mesh.faces().len(), 4);
+        assert_consistent(&mesh);
+        for i in 0..mesh.halfedges().len() {
+            assert!(!mesh.is_boundary_halfedge(HalfedgeHandle::from_index(i)));
+        }
+        for &vh in v.iter() {
+            assert!(!mesh.is_boundary_vertex(vh));
+        }
+    }
+
+    #[test]
+    fn patch_relinking() {
+        // Three triangles attached at the center vertex 0 only, whose boundary halfedges around
+        // vertex 0 are linked in an order that requires re-linking to fill the gaps.
+        let (mut mesh, v) = mesh_with_vertices(7);
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        mesh.add_face(&[v[0], v[3], v[4]]).unwrap();
+        mesh.add_face(&[v[0], v[5], v[6]]).unwrap();
+        assert_consistent(&mesh);
+        mesh.add_face(&[v[0], v[4], v[5]]).unwrap();
+        assert_consistent(&mesh);
+        mesh.add_face(&[v[0], v[2], v[3]]).unwrap();
+        assert_consistent(&mesh);
+        assert!(mesh.is_boundary_vertex(v[0]));
+        mesh.add_face(&[v[0], v[6], v[1]]).unwrap();
+        assert_consistent(&mesh);
+        assert!(!mesh.is_boundary_vertex(v[0]));
+    }
+
+    #[test]
+    fn errors() {
+        let (mut mesh, v) = mesh_with_vertices(6);
+        assert_eq!(
+            mesh.add_face(&[v[0], v[1]]),
+            Err(TopologyError::DegenerateFace)
+        );
+        assert_eq!(
+            mesh.add_face(&[v[0], v[1], v[0]]),
+            Err(TopologyError::DegenerateFace)
+        );
+        let bad = VertexHandle::from_index(100);
+        assert_eq!(
+            mesh.add_face(&[v[0], v[1], bad]),
+            Err(TopologyError::InvalidVertex(bad))
+        );
+
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        assert_eq!(
+            mesh.add_face(&[v[1], v[0], v[3]]),
+            Ok(FaceHandle::from_index(1))
+        );
+        // Both sides of (v0, v1) are now taken.
+        assert_eq!(
+            mesh.add_face(&[v[0], v[1], v[4]]),
+            Err(TopologyError::ComplexEdge(v[0], v[1]))
+        );
+        assert_eq!(
+            mesh.add_face(&[v[1], v[2], v[4]]),
+            Err(TopologyError::FlippedOrientation(v[1], v[2]))
+        );
+        mesh.add_face(&[v[0], v[2], v[4]]).unwrap();
+        mesh.add_face(&[v[0], v[4], v[3]]).unwrap();
+        assert!(!mesh.is_boundary_vertex(v[0]));
+        // Unchanged by the failures above.
+        assert_eq!(mesh.faces().len(), 4);
+        assert_consistent(&mesh);
+        assert_eq!(
+            mesh.add_face(&[v[0], v[5], v[2]]),
+            Err(TopologyError::ComplexVertex(v[0]))
+        );
+    }
+
+    #[test]
+    fn patch_relinking_failed() {
+        // Closing the triangle (0, 1, 2) at vertex 0 would leave the other triangle at vertex 0
+        // with no gap to be moved into.
+        let (mut mesh, v) = mesh_with_vertices(5);
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        mesh.add_face(&[v[0], v[3], v[4]]).unwrap();
+        assert_eq!(
+            mesh.add_face(&[v[1], v[0], v[2]]),
+            Err(TopologyError::PatchRelinkingFailed(v[0]))
+        );
+        assert_eq!(mesh.faces().len(), 2);
+        assert_eq!(mesh.edges().len(), 6);
+        assert_consistent(&mesh);
+    }
+}
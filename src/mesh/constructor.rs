@@ -14,17 +14,29 @@ impl Mesh {
     }
 
     /// Creates a mesh from the given parts. This is a low-level crate-internal function.
+    /// The property lists are sized to match the items, with default-initialized values.
     pub(crate) fn from_parts(vertices: Vec<Vertex>, edges: Vec<Edge>, faces: Vec<Face>) -> Mesh {
-        Mesh {
+        let mut mesh = Mesh {
             vertices,
             edges,
             faces,
             ..Default::default()
-        }
+        };
+        let (nv, nh, ne, nf) = (
+            mesh.vertices().len(),
+            mesh.halfedges().len(),
+            mesh.edges().len(),
+            mesh.faces().len(),
+        );
+        mesh.v_props.resize_all(nv);
+        mesh.h_props.resize_all(nh);
+        mesh.e_props.resize_all(ne);
+        mesh.f_props.resize_all(nf);
+        mesh
     }
 
     /// Returns a mesh representing this triangle.
-    pub fn triangle(_p1: Vec3<f64>, _p2: Vec3<f64>, _p3: Vec3<f64>) -> Mesh {
+    pub fn triangle(p1: Vec3<f64>, p2: Vec3<f64>, p3: Vec3<f64>) -> Mesh {
         let vh = VertexHandle::from_index;
         let hh = HalfedgeHandle::from_index;
         let fh = FaceHandle::from_index;
@@ -36,9 +48,9 @@ impl Mesh {
         //   /|/___4___\\ \|
         //  1 <----5----- 2
         let vertices = vec![
-            Vertex { hh: hh(0) },
-            Vertex { hh: hh(2) },
-            Vertex { hh: hh(4) },
+            Vertex { hh: hh(1) },
+            Vertex { hh: hh(3) },
+            Vertex { hh: hh(5) },
         ];
         let faces = vec![Face { hh: hh(0) }];
         #[rustfmt::skip]
@@ -56,8 +68,9 @@ impl Mesh {
                 Halfedge { fh: inval, vh: vh(1), hnext: hh(3), hprev: hh(1) }, // hh 5
             ]),
         ];
-        // TODO: Actually use the positions by adding a position property.
-        Mesh::from_parts(vertices, edges, faces)
+        let mut mesh = Mesh::from_parts(vertices, edges, faces);
+        mesh.points_mut().copy_from_slice(&[p1, p2, p3]);
+        mesh
     }
 
     /// Returns a mesh representing `num_tri` triangles for testing.
@@ -80,9 +93,9 @@ impl Mesh {
             let vi = i * 3;
             let hi = i * 6;
             let fh = fh(i);
-            vertices.push(Vertex { hh: hh(hi) });
-            vertices.push(Vertex { hh: hh(hi + 2) });
-            vertices.push(Vertex { hh: hh(hi + 4) });
+            vertices.push(Vertex { hh: hh(hi + 1) });
+            vertices.push(Vertex { hh: hh(hi + 3) });
+            vertices.push(Vertex { hh: hh(hi + 5) });
             faces.push(Face { hh: hh(hi) });
             #[rustfmt::skip]
             edges.extend([
@@ -92,11 +105,11 @@ impl Mesh {
                 ]),
                 Edge([ // hh 2, hh 3
                     Halfedge { fh       , vh: vh(vi + 1), hnext: hh(hi + 4), hprev: hh(hi    ) },
-                    Halfedge { fh: inval, vh: vh(vi + 1), hnext: hh(hi + 1), hprev: hh(hi + 5) },
+                    Halfedge { fh: inval, vh: vh(vi    ), hnext: hh(hi + 1), hprev: hh(hi + 5) },
                 ]),
                 Edge([ // hh 4, hh 5
                     Halfedge { fh       , vh: vh(vi + 2), hnext: hh(hi    ), hprev: hh(hi + 2) },
-                    Halfedge { fh: inval, vh: vh(vi    ), hnext: hh(hi + 3), hprev: hh(hi + 1) },
+                    Halfedge { fh: inval, vh: vh(vi + 1), hnext: hh(hi + 3), hprev: hh(hi + 1) },
                 ]),
             ]);
         }
@@ -127,4 +140,13 @@ mod test {
         assert_eq!(mesh.edges().len(), 3);
         assert_eq!(mesh.faces().len(), 1);
     }
+
+    #[test]
+    fn triangle_points() {
+        let p1 = Vec3::new(0.0, 0.0, 1.0);
+        let p2 = Vec3::new(-1.0, 0.0, 0.0);
+        let p3 = Vec3::new(1.0, 0.0, 0.0);
+        let mesh = Mesh::triangle(p1, p2, p3);
+        assert_eq!(mesh.points(), &[p1, p2, p3]);
+    }
 }
@@ -0,0 +1,323 @@
+//! Item deletion, in the spirit of OpenMesh's `PolyConnectivity`.
+//!
+//! Deleted items are only marked with the `Status::DELETED` flag and unlinked from the rest of the
+//! mesh; they remain in storage until garbage collection. Deletion therefore requires the vertex,
+//! edge, and face status properties (see `Mesh::request_vertex_status()` etc.). The halfedge
+//! status is updated only if it has been requested.
+use crate::mesh::item_handle::{
+    EdgeHandle, FaceHandle, HalfedgeHandle, MeshItemHandle, VertexHandle,
+};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::status::Status;
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+impl Mesh {
+    /// The status of `h`, which is empty if the status property for its item type is missing.
+    pub(crate) fn item_status<H: MeshItemHandle>(&self, h: H) -> Status {
+        H::status_prop(self).map(|prop| prop[h]).unwrap_or_default()
+    }
+
+    /// Whether `h` is marked DELETED. Items without status are never deleted.
+    pub(crate) fn is_deleted<H: MeshItemHandle>(&self, h: H) -> bool {
+        self.item_status(h).deleted()
+    }
+
+    /// Marks `h` as DELETED if the status property for its item type exists.
+    pub(crate) fn set_deleted<H: MeshItemHandle>(&mut self, h: H) {
+        if let Some(prop) = H::status_prop_mut(self) {
+            prop[h].set_deleted(true);
+        }
+    }
+
+    /// Fails unless the status properties that deletion updates exist.
+    pub(crate) fn check_deletion_status(&self) -> Result<()> {
+        if self.get_vertex_status().is_none() {
+            Err(TopologyError::MissingStatus("vertex"))
+        } else if self.get_edge_status().is_none() {
+            Err(TopologyError::MissingStatus("edge"))
+        } else if self.get_face_status().is_none() {
+            Err(TopologyError::MissingStatus("face"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Deletes the vertex `vh` along with all its incident faces and edges.
+    ///
+    /// If `delete_isolated_vertices` is true, then neighboring vertices left without any edges
+    /// are deleted as well. Deleting an already-deleted vertex does nothing.
+    ///
+    /// Fails if the vertex, edge, or face status property is missing.
+    pub fn delete_vertex(
+        &mut self,
+        vh: VertexHandle,
+        delete_isolated_vertices: bool,
+    ) -> Result<()> {
+        self.check_deletion_status()?;
+        if self.is_deleted(vh) {
+            return Ok(());
+        }
+        // Collect the faces up front since deleting them re-links the halfedges around `vh`.
+        let fhs = self.vf_ccw_iter(vh).collect::<Vec<_>>();
+        for fh in fhs {
+            self.delete_face_unchecked(fh, delete_isolated_vertices);
+        }
+        self.set_deleted(vh);
+        Ok(())
+    }
+
+    /// Deletes the edge `eh` along with its incident faces.
+    ///
+    /// If `delete_isolated_vertices` is true, then vertices left without any edges are deleted as
+    /// well. Deleting an already-deleted edge does nothing.
+    ///
+    /// Fails if the vertex, edge, or face status property is missing.
+    pub fn delete_edge(&mut self, eh: EdgeHandle, delete_isolated_vertices: bool) -> Result<()> {
+        self.check_deletion_status()?;
+        if self.is_deleted(eh) {
+            return Ok(());
+        }
+        let fh0 = self.face(self.edge_halfedge(eh, 0));
+        let fh1 = self.face(self.edge_halfedge(eh, 1));
+        if fh0.is_valid() {
+            self.delete_face_unchecked(fh0, delete_isolated_vertices);
+        }
+        if fh1.is_valid() {
+            self.delete_face_unchecked(fh1, delete_isolated_vertices);
+        }
+        // Edges are otherwise removed along with their last face.
+        if !fh0.is_valid() && !fh1.is_valid() {
+            self.remove_edge(eh, delete_isolated_vertices);
+        }
+        Ok(())
+    }
+
+    /// Deletes the face `fh`. Its halfedges become boundary halfedges, and the edges that are left
+    /// without any faces are deleted.
+    ///
+    /// If `delete_isolated_vertices` is true, then vertices left without any edges are deleted as
+    /// well. Deleting an already-deleted face does nothing.
+    ///
+    /// Fails if the vertex, edge, or face status property is missing.
+    pub fn delete_face(&mut self, fh: FaceHandle, delete_isolated_vertices: bool) -> Result<()> {
+        self.check_deletion_status()?;
+        if !self.is_deleted(fh) {
+            self.delete_face_unchecked(fh, delete_isolated_vertices);
+        }
+        Ok(())
+    }
+
+    /// Implements `delete_face()` without the status checks.
+    fn delete_face_unchecked(&mut self, fh: FaceHandle, delete_isolated_vertices: bool) {
+        self.set_deleted(fh);
+
+        // Turn the face's halfedges into boundary halfedges, collecting the edges which are now
+        // boundary on both sides.
+        let hhs = self.fh_ccw_iter(fh).collect::<Vec<_>>();
+        let mut removed_edges = Vec::with_capacity(hhs.len());
+        let mut vhs = Vec::with_capacity(hhs.len());
+        for &hh in &hhs {
+            self.set_face(hh, FaceHandle::new());
+            if self.is_boundary_halfedge(self.opposite_halfedge(hh)) {
+                removed_edges.push(self.edge(hh));
+            }
+            vhs.push(self.to_vertex(hh));
+        }
+
+        for eh in removed_edges {
+            self.remove_edge(eh, delete_isolated_vertices);
+        }
+        for vh in vhs {
+            self.adjust_outgoing_halfedge(vh);
+        }
+    }
+
+    /// Unlinks the edge `eh`, whose halfedges must both be boundary, from the halfedge loops
+    /// around its vertices and marks it as DELETED. Vertices left without edges become isolated,
+    /// and are deleted if `delete_isolated_vertices` is true.
+    fn remove_edge(&mut self, eh: EdgeHandle, delete_isolated_vertices: bool) {
+        let hh0 = self.edge_halfedge(eh, 0);
+        let hh1 = self.edge_halfedge(eh, 1);
+        let (v0, next0, prev0) = self.unlink_data(hh0);
+        let (v1, next1, prev1) = self.unlink_data(hh1);
+        debug_assert!(self.is_boundary_halfedge(hh0) && self.is_boundary_halfedge(hh1));
+
+        self.set_next_halfedge(prev0, next1);
+        self.set_next_halfedge(prev1, next0);
+
+        self.set_deleted(eh);
+        self.set_deleted(hh0);
+        self.set_deleted(hh1);
+
+        // `next0` leaves `v0` and `next1` leaves `v1`, unless the vertex has no other edges.
+        for &(vh, outgoing, next) in &[(v0, hh1, next0), (v1, hh0, next1)] {
+            if self.vertex_halfedge(vh) == outgoing {
+                if next == outgoing {
+                    if delete_isolated_vertices {
+                        self.set_deleted(vh);
+                    }
+                    self.set_vertex_halfedge(vh, HalfedgeHandle::new());
+                } else {
+                    self.set_vertex_halfedge(vh, next);
+                }
+            }
+        }
+    }
+
+    /// The to-vertex, next, and previous halfedges of `hh`.
+    fn unlink_data(&self, hh: HalfedgeHandle) -> (VertexHandle, HalfedgeHandle, HalfedgeHandle) {
+        (
+            self.to_vertex(hh),
+            self.next_halfedge(hh),
+            self.prev_halfedge(hh),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, fan, request_status};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    #[test]
+    fn missing_status() {
+        let mut mesh = Mesh::new();
+        let v = (0..3)
+            .map(|_| mesh.add_vertex(Vec3::new(0.0, 0.0, 0.0)))
+            .collect::<Vec<_>>();
+        let fh = mesh.add_face(&v).unwrap();
+        assert_eq!(
+            mesh.delete_face(fh, false),
+            Err(TopologyError::MissingStatus("vertex"))
+        );
+        mesh.request_vertex_status();
+        assert_eq!(
+            mesh.delete_edge(EdgeHandle::from_index(0), false),
+            Err(TopologyError::MissingStatus("edge"))
+        );
+        mesh.request_edge_status();
+        assert_eq!(
+            mesh.delete_vertex(v[0], false),
+            Err(TopologyError::MissingStatus("face"))
+        );
+        mesh.request_face_status();
+        assert_eq!(mesh.delete_face(fh, false), Ok(()));
+    }
+
+    #[test]
+    fn delete_face() {
+        let (mut mesh, v, f) = fan(4);
+        request_status(&mut mesh);
+        mesh.delete_face(f[0], false).unwrap();
+        assert!(mesh.is_deleted(f[0]));
+        assert_eq!(mesh.faces().iter().count(), 3);
+        // Only the ring edge (v1, v2) is left without faces.
+        assert_eq!(mesh.edges().iter().count(), 7);
+        assert!(!mesh.find_halfedge(v[1], v[2]).is_valid());
+        assert!(mesh.is_boundary(v[0]));
+        assert!(mesh.is_boundary(mesh.vertex_halfedge(v[0])));
+        assert_eq!(mesh.vf_ccw_iter(v[0]).count(), 3);
+        assert_consistent(&mesh);
+
+        // Deleting again is a no-op.
+        mesh.delete_face(f[0], false).unwrap();
+        assert_eq!(mesh.edges().iter().count(), 7);
+        assert_consistent(&mesh);
+
+        // Now deleting the neighboring face also removes the spoke (v0, v2) and the ring edge
+        // (v2, v3).
+        mesh.delete_face(f[1], false).unwrap();
+        assert_eq!(mesh.faces().iter().count(), 2);
+        assert_eq!(mesh.edges().iter().count(), 5);
+        assert!(!mesh.find_halfedge(v[0], v[2]).is_valid());
+        // `v[2]` is isolated, but not deleted.
+        assert!(!mesh.vertex_halfedge(v[2]).is_valid());
+        assert!(!mesh.is_deleted(v[2]));
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn delete_face_isolated_vertices() {
+        let (mut mesh, v, f) = fan(4);
+        request_status(&mut mesh);
+        mesh.delete_face(f[0], true).unwrap();
+        mesh.delete_face(f[1], true).unwrap();
+        assert!(mesh.is_deleted(v[2]));
+        assert_eq!(mesh.vertices().iter().count(), 4);
+        assert_consistent(&mesh);
+
+        mesh.delete_face(f[2], true).unwrap();
+        mesh.delete_face(f[3], true).unwrap();
+        assert_eq!(mesh.vertices().iter().count(), 0);
+        assert_eq!(mesh.edges().iter().count(), 0);
+        assert_eq!(mesh.halfedges().iter().count(), 0);
+    }
+
+    #[test]
+    fn delete_edge() {
+        let (mut mesh, v, f) = fan(4);
+        request_status(&mut mesh);
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[1]));
+        mesh.delete_edge(eh, false).unwrap();
+        assert!(mesh.is_deleted(eh));
+        assert!(mesh.is_deleted(f[0]));
+        assert!(mesh.is_deleted(f[3]));
+        assert_eq!(mesh.faces().iter().count(), 2);
+        // The ring edges at `v[1]` go along with the faces.
+        assert_eq!(mesh.edges().iter().count(), 5);
+        assert!(!mesh.vertex_halfedge(v[1]).is_valid());
+        assert!(!mesh.is_deleted(v[1]));
+        assert_consistent(&mesh);
+
+        // Deleting an interior edge.
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[3]));
+        mesh.delete_edge(eh, true).unwrap();
+        assert_eq!(mesh.faces().iter().count(), 0);
+        assert_eq!(mesh.edges().iter().count(), 0);
+        assert_eq!(mesh.vertices().iter().count(), 1);
+        assert!(mesh.vertices().iter().eq(vec![v[1]]));
+    }
+
+    #[test]
+    fn delete_vertex() {
+        let (mut mesh, v, _) = fan(4);
+        request_status(&mut mesh);
+        mesh.delete_vertex(v[1], false).unwrap();
+        assert!(mesh.is_deleted(v[1]));
+        assert!(!mesh.vertex_halfedge(v[1]).is_valid());
+        assert_eq!(mesh.faces().iter().count(), 2);
+        assert_eq!(mesh.edges().iter().count(), 5);
+        // Circulation starts at the new boundary.
+        assert_eq!(
+            mesh.vv_ccw_iter(v[0]).collect::<Vec<_>>(),
+            vec![v[4], v[2], v[3]]
+        );
+        assert_consistent(&mesh);
+
+        mesh.delete_vertex(v[0], true).unwrap();
+        assert_eq!(mesh.faces().iter().count(), 0);
+        assert_eq!(mesh.edges().iter().count(), 0);
+        assert_eq!(mesh.vertices().iter().count(), 0);
+    }
+
+    #[test]
+    fn halfedge_status() {
+        let (mut mesh, v, _) = fan(4);
+        request_status(&mut mesh);
+        mesh.delete_face(FaceHandle::from_index(0), false).unwrap();
+        let deleted = mesh
+            .halfedges()
+            .iter_all()
+            .filter(|&hh| mesh.is_deleted(hh))
+            .collect::<Vec<HalfedgeHandle>>();
+        assert_eq!(deleted.len(), 2);
+        for hh in deleted {
+            assert!(mesh.to_vertex(hh) == v[1] || mesh.from_vertex(hh) == v[1]);
+        }
+    }
+}
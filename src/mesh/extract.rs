@@ -0,0 +1,232 @@
+//! Extraction of submeshes, which copies parts of a mesh into a new, compact mesh.
+use crate::mesh::handle_map::{ExtractMap, HandleMap};
+use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::mesh::items::{Edge, Face, Halfedge, Vertex};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::{Handle, Index};
+
+impl Mesh {
+    /// Copies the faces `fhs` along with their edges and vertices into a new, compact mesh, and
+    /// returns it along with the map from the handles of `self` to its handles. DELETED and
+    /// repeated faces are skipped.
+    ///
+    /// Items keep their relative order, and all their properties are copied along with them, so
+    /// property handles of `self` remain valid for the new mesh. Halfedges whose faces are not
+    /// extracted become boundary halfedges.
+    pub fn extract(&self, fhs: impl Iterator<Item = FaceHandle>) -> (Mesh, ExtractMap) {
+        let mut has_face = vec![false; self.faces.len()];
+        let mut has_vertex = vec![false; self.vertices.len()];
+        for fh in fhs {
+            if self.is_deleted(fh) {
+                continue;
+            }
+            has_face[fh.index_us()] = true;
+            for vh in self.fv_ccw_iter(fh) {
+                has_vertex[vh.index_us()] = true;
+            }
+        }
+        let vhs: Vec<VertexHandle> = (0..self.vertices.len())
+            .filter(|&i| has_vertex[i])
+            .map(|i| VertexHandle::from_index(i as Index))
+            .collect();
+        let fhs: Vec<FaceHandle> = (0..self.faces.len())
+            .filter(|&i| has_face[i])
+            .map(|i| FaceHandle::from_index(i as Index))
+            .collect();
+        self.submesh(&vhs, &fhs)
+    }
+
+    /// Extracts the faces marked SELECTED like `Mesh::extract()`. The face status must be
+    /// available.
+    pub fn extract_selected(&self) -> Result<(Mesh, ExtractMap)> {
+        let status = self
+            .get_face_status()
+            .ok_or(TopologyError::MissingStatus("face"))?;
+        Ok(self.extract(self.faces().iter_all().filter(|&fh| status[fh].selected())))
+    }
+
+    /// A new mesh with the vertices `vhs` and faces `fhs` of `self`, in that order, along with
+    /// the edges of those faces in the order of their handles, and the map to its handles. All
+    /// properties are copied, and property handles of `self` remain valid for the new mesh.
+    ///
+    /// The vertices of the faces must all be in `vhs`. Halfedges whose face is not in `fhs`
+    /// become boundary halfedges of the new mesh.
+    pub(crate) fn submesh(&self, vhs: &[VertexHandle], fhs: &[FaceHandle]) -> (Mesh, ExtractMap) {
+        let mut vmap = HandleMap::from_vec(vec![VertexHandle::new(); self.vertices.len()]);
+        for (i, &vh) in vhs.iter().enumerate() {
+            vmap.set(vh, VertexHandle::from_index(i as Index));
+        }
+        let mut fmap = HandleMap::from_vec(vec![FaceHandle::new(); self.faces.len()]);
+        for (i, &fh) in fhs.iter().enumerate() {
+            fmap.set(fh, FaceHandle::from_index(i as Index));
+        }
+        let mut has_edge = vec![false; self.edges.len()];
+        for &fh in fhs {
+            for hh in self.fh_ccw_iter(fh) {
+                has_edge[self.edge(hh).index_us()] = true;
+            }
+        }
+        let ehs: Vec<EdgeHandle> = (0..self.edges.len())
+            .filter(|&i| has_edge[i])
+            .map(|i| EdgeHandle::from_index(i as Index))
+            .collect();
+        let hhs: Vec<HalfedgeHandle> = ehs
+            .iter()
+            .flat_map(|&eh| (0..2).map(move |i| self.edge_halfedge(eh, i)))
+            .collect();
+        let mut emap = HandleMap::from_vec(vec![EdgeHandle::new(); self.edges.len()]);
+        for (i, &eh) in ehs.iter().enumerate() {
+            emap.set(eh, EdgeHandle::from_index(i as Index));
+        }
+        let mut hmap = HandleMap::from_vec(vec![HalfedgeHandle::new(); 2 * self.edges.len()]);
+        for (i, &hh) in hhs.iter().enumerate() {
+            hmap.set(hh, HalfedgeHandle::from_index(i as Index));
+        }
+        let kept = |hh: HalfedgeHandle| has_edge[self.edge(hh).index_us()];
+
+        // The next halfedge of a new boundary halfedge is the first kept halfedge clockwise
+        // around its to-vertex, which exists since its opposite halfedge is kept.
+        let hnexts: Vec<HalfedgeHandle> = hhs
+            .iter()
+            .map(|&hh| {
+                let mut next = self.next_halfedge(hh);
+                if !fmap.get(self.face(hh)).is_valid() {
+                    while !kept(next) {
+                        next = self.cw_rotated(next);
+                    }
+                }
+                hmap.get(next)
+            })
+            .collect();
+        let mut hprevs = vec![HalfedgeHandle::new(); hhs.len()];
+        for (i, hnext) in hnexts.iter().enumerate() {
+            hprevs[hnext.index_us()] = HalfedgeHandle::from_index(i as Index);
+        }
+        let halfedge = |i: usize| {
+            let hh = hhs[i];
+            Halfedge {
+                fh: fmap.get(self.face(hh)),
+                vh: vmap.get(self.to_vertex(hh)),
+                hnext: hnexts[i],
+                hprev: hprevs[i],
+            }
+        };
+
+        let mut mesh = Mesh {
+            vertices: vhs
+                .iter()
+                .map(|&vh| Vertex {
+                    hh: hmap.get(
+                        self.voh_ccw_iter(vh)
+                            .find(|&hh| kept(hh))
+                            .unwrap_or_default(),
+                    ),
+                })
+                .collect(),
+            edges: (0..ehs.len())
+                .map(|i| Edge([halfedge(2 * i), halfedge(2 * i + 1)]))
+                .collect(),
+            faces: fhs
+                .iter()
+                .map(|&fh| Face {
+                    hh: hmap.get(self.face_halfedge(fh)),
+                })
+                .collect(),
+            v_props: self.v_props.gather(vhs),
+            h_props: self.h_props.gather(&hhs),
+            e_props: self.e_props.gather(&ehs),
+            f_props: self.f_props.gather(fhs),
+            m_props: self.m_props.clone(),
+            v_status: self.v_status.clone(),
+            h_status: self.h_status.clone(),
+            e_status: self.e_status.clone(),
+            f_status: self.f_status.clone(),
+            points: self.points,
+        };
+        for i in 0..vhs.len() {
+            mesh.adjust_outgoing_halfedge(VertexHandle::from_index(i as Index));
+        }
+        let map = ExtractMap {
+            vertices: vmap,
+            halfedges: hmap,
+            edges: emap,
+            faces: fmap,
+        };
+        (mesh, map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mesh::item_handle::{FaceHandle, HalfedgeHandle, VertexHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, grid, request_status};
+    use crate::property::{Handle, Index};
+
+    #[test]
+    fn extract() {
+        // The faces (0, 1) and (1, 1) of a 3x3 grid, which share an edge.
+        let (mut mesh, v) = grid(3);
+        let prop = mesh.h_props_mut().add::<u32>(Some("index".into()));
+        for i in 0..mesh.halfedges().len() {
+            mesh.h_props_mut().get_mut(prop).unwrap()[HalfedgeHandle::from_index(i)] = i;
+        }
+        let fhs = [FaceHandle::from_index(4), FaceHandle::from_index(1)];
+        let (sub, map) = mesh.extract(fhs.iter().cloned().chain(Some(fhs[0])));
+        assert_consistent(&sub);
+        assert_eq!(sub.vertices().len(), 6);
+        assert_eq!(sub.edges().len(), 7);
+        assert_eq!(sub.faces().len(), 2);
+        assert_eq!(sub.boundary_loops().len(), 1);
+
+        // Relative order is kept.
+        assert_eq!(map.faces.get(fhs[1]), FaceHandle::from_index(0));
+        assert_eq!(map.faces.get(fhs[0]), FaceHandle::from_index(1));
+        assert_eq!(map.vertices.get(v[1]), VertexHandle::from_index(0));
+        assert!(!map.vertices.get(v[0]).is_valid());
+        assert!(!map.faces.get(FaceHandle::from_index(0)).is_valid());
+
+        for &fh in &fhs {
+            let mut vhs: Vec<_> = mesh.fv_ccw_iter(fh).collect();
+            map.vertices.update(&mut vhs);
+            assert_eq!(sub.fv_ccw_iter(map.faces.get(fh)).collect::<Vec<_>>(), vhs);
+            for vh in mesh.fv_ccw_iter(fh) {
+                assert_eq!(sub.point(map.vertices.get(vh)), mesh.point(vh));
+            }
+        }
+        let values = sub.h_props().get(prop).unwrap();
+        for (old, &new) in map.halfedges.as_slice().iter().enumerate() {
+            if new.is_valid() {
+                assert_eq!(values[new], old as Index);
+            }
+        }
+    }
+
+    #[test]
+    fn extract_selected() {
+        let (mut mesh, _) = grid(2);
+        assert_eq!(
+            mesh.extract_selected().err(),
+            Some(TopologyError::MissingStatus("face"))
+        );
+        request_status(&mut mesh);
+        let (sub, _) = mesh.extract_selected().unwrap();
+        assert_eq!(sub.vertices().len(), 0);
+        assert_eq!(sub.faces().len(), 0);
+
+        for i in &[0, 3] {
+            mesh.get_face_status_mut().unwrap()[FaceHandle::from_index(*i)].set_selected(true);
+        }
+        mesh.delete_face(FaceHandle::from_index(3), false).unwrap();
+        let (sub, map) = mesh.extract_selected().unwrap();
+        assert_consistent(&sub);
+        assert_eq!(sub.faces().len(), 1);
+        assert_eq!(sub.vertices().len(), 4);
+        assert!(sub.get_face_status().unwrap()[FaceHandle::from_index(0)].selected());
+        assert_eq!(
+            map.faces.get(FaceHandle::from_index(0)),
+            FaceHandle::from_index(0)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Edge flip for triangles, in the spirit of OpenMesh's `TriConnectivity::flip()`.
+use crate::mesh::item_handle::EdgeHandle;
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+
+impl Mesh {
+    /// Whether the edge `eh` can be flipped by `flip()`. This fails if
+    ///
+    /// - the edge is DELETED or on the boundary,
+    /// - either face incident to the edge is not a triangle, or
+    /// - the flipped edge already exists in the mesh.
+    pub fn is_flip_ok(&self, eh: EdgeHandle) -> bool {
+        if self.is_deleted(eh) || self.is_boundary_edge(eh) {
+            return false;
+        }
+        let hh = self.edge_halfedge(eh, 0);
+        let oh = self.edge_halfedge(eh, 1);
+        if self.face_valence(self.face(hh)) != 3 || self.face_valence(self.face(oh)) != 3 {
+            return false;
+        }
+
+        // The flipped edge connects the tips of the two triangles.
+        let ah = self.to_vertex(self.next_halfedge(hh));
+        let bh = self.to_vertex(self.next_halfedge(oh));
+        ah != bh && !self.vv_ccw_iter(ah).any(|vh| vh == bh)
+    }
+
+    /// Flips the edge `eh` shared by two triangles, so that it connects the two vertices
+    /// opposite to it. The edge, its halfedges, and the two faces keep their handles and
+    /// properties.
+    ///
+    /// Fails without changing the mesh if the edge cannot be flipped. See `is_flip_ok()`.
+    pub fn flip(&mut self, eh: EdgeHandle) -> Result<()> {
+        if !self.is_flip_ok(eh) {
+            return Err(TopologyError::FlipNotOk(eh));
+        }
+
+        let a0 = self.edge_halfedge(eh, 0);
+        let b0 = self.edge_halfedge(eh, 1);
+        let a1 = self.next_halfedge(a0);
+        let a2 = self.next_halfedge(a1);
+        let b1 = self.next_halfedge(b0);
+        let b2 = self.next_halfedge(b1);
+        let va0 = self.to_vertex(a0);
+        let va1 = self.to_vertex(a1);
+        let vb0 = self.to_vertex(b0);
+        let vb1 = self.to_vertex(b1);
+        let fa = self.face(a0);
+        let fb = self.face(b0);
+
+        self.set_to_vertex(a0, va1);
+        self.set_to_vertex(b0, vb1);
+
+        self.set_next_halfedge(a0, a2);
+        self.set_next_halfedge(a2, b1);
+        self.set_next_halfedge(b1, a0);
+
+        self.set_next_halfedge(b0, b2);
+        self.set_next_halfedge(b2, a1);
+        self.set_next_halfedge(a1, b0);
+
+        self.set_face(a1, fb);
+        self.set_face(b1, fa);
+
+        self.set_face_halfedge(fa, a0);
+        self.set_face_halfedge(fb, b0);
+
+        // The old endpoints may have been anchored at the flipped halfedges.
+        if self.vertex_halfedge(va0) == b0 {
+            self.set_vertex_halfedge(va0, a1);
+        }
+        if self.vertex_halfedge(vb0) == a0 {
+            self.set_vertex_halfedge(vb0, b1);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{
+        assert_consistent, fan, grid, mesh_with_vertices, request_status,
+    };
+    use crate::property::Handle;
+
+    #[test]
+    fn flip() {
+        let (mut mesh, v) = mesh_with_vertices(4);
+        let f0 = mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        let f1 = mesh.add_face(&[v[0], v[2], v[3]]).unwrap();
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[2]));
+        assert!(mesh.is_flip_ok(eh));
+        mesh.flip(eh).unwrap();
+        assert_consistent(&mesh);
+        assert!(!mesh.find_halfedge(v[0], v[2]).is_valid());
+        let hh = mesh.find_halfedge(v[1], v[3]);
+        assert_eq!(mesh.edge(hh), eh);
+        assert_eq!(mesh.faces().len(), 2);
+        for &fh in &[f0, f1] {
+            assert_eq!(mesh.face_valence(fh), 3);
+            assert!(mesh.fv_ccw_iter(fh).any(|vh| vh == v[1]));
+            assert!(mesh.fv_ccw_iter(fh).any(|vh| vh == v[3]));
+        }
+
+        // Flipping back restores the original edge.
+        mesh.flip(eh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.edge(mesh.find_halfedge(v[0], v[2])), eh);
+    }
+
+    #[test]
+    fn flip_updates_anchors() {
+        let (mut mesh, v, _) = fan(4);
+        for i in 1..=4 {
+            let eh = mesh.edge(mesh.find_halfedge(v[0], v[i]));
+            if mesh.is_flip_ok(eh) {
+                mesh.flip(eh).unwrap();
+                assert_consistent(&mesh);
+                for &vh in &v {
+                    let hh = mesh.vertex_halfedge(vh);
+                    assert!(!hh.is_valid() || mesh.from_vertex(hh) == vh);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flip_not_ok() {
+        // Boundary edge.
+        let (mut mesh, v, _) = fan(4);
+        let eh = mesh.edge(mesh.find_halfedge(v[1], v[2]));
+        assert!(!mesh.is_flip_ok(eh));
+        assert_eq!(mesh.flip(eh), Err(TopologyError::FlipNotOk(eh)));
+
+        // The flipped edge (v2, v3) would duplicate an existing edge.
+        let (mut mesh, v, _) = fan(3);
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[1]));
+        assert!(!mesh.is_flip_ok(eh));
+        assert_eq!(mesh.flip(eh), Err(TopologyError::FlipNotOk(eh)));
+
+        // Deleted edge.
+        let (mut mesh, v, _) = fan(4);
+        request_status(&mut mesh);
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[1]));
+        mesh.delete_edge(eh, false).unwrap();
+        assert!(!mesh.is_flip_ok(eh));
+
+        // Quads.
+        let (mesh, v) = grid(2);
+        let eh = mesh.edge(mesh.find_halfedge(v[1], v[4]));
+        assert!(!mesh.is_flip_ok(eh));
+    }
+}
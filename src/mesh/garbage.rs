@@ -0,0 +1,233 @@
+//! Garbage collection, which removes the items marked DELETED from storage.
+use crate::mesh::handle_map::{GarbageCollectionMap, HandleMap};
+use crate::mesh::item_handle::{
+    EdgeHandle, FaceHandle, HalfedgeHandle, MeshItemHandle, VertexHandle,
+};
+use crate::mesh::Mesh;
+use crate::property::{Handle, Index, Size};
+
+impl Mesh {
+    /// Removes all vertices, edges, and faces marked DELETED (see `Mesh::delete_face()` etc.)
+    /// from storage, along with their properties. Items without status are never removed.
+    ///
+    /// Remaining items are compacted by moving items from the end of storage into the slots of
+    /// deleted ones, so their handles change. All handles stored in the mesh are updated, and the
+    /// returned map can be used to update any handles stored outside of it, e.g. via
+    /// `map.vertices.update(&mut vhs)`. References to removed items become invalid handles.
+    pub fn garbage_collection(&mut self) -> GarbageCollectionMap {
+        let (vertices, nv) = self.compact(Mesh::swap_items::<VertexHandle>);
+        let (edges, ne) = self.compact(Mesh::swap_edges);
+        let (faces, nf) = self.compact(Mesh::swap_items::<FaceHandle>);
+        let halfedges = HandleMap::from_vec(
+            (0..2 * edges.len())
+                .map(|i| {
+                    let eh = edges.get(EdgeHandle::from_index(i / 2));
+                    if eh.is_valid() {
+                        HalfedgeHandle::from_index(2 * eh.index() + i % 2)
+                    } else {
+                        HalfedgeHandle::new()
+                    }
+                })
+                .collect(),
+        );
+
+        for vertex in &mut self.vertices[..nv as usize] {
+            vertex.hh = halfedges.get(vertex.hh);
+        }
+        for edge in &mut self.edges[..ne as usize] {
+            for halfedge in edge.0.iter_mut() {
+                halfedge.vh = vertices.get(halfedge.vh);
+                halfedge.fh = faces.get(halfedge.fh);
+                halfedge.hnext = halfedges.get(halfedge.hnext);
+                halfedge.hprev = halfedges.get(halfedge.hprev);
+            }
+        }
+        for face in &mut self.faces[..nf as usize] {
+            face.hh = halfedges.get(face.hh);
+        }
+        self.resize(nv, ne, nf);
+
+        GarbageCollectionMap {
+            vertices,
+            halfedges,
+            edges,
+            faces,
+        }
+    }
+
+    /// Moves the items of type `H` not marked DELETED to the front of storage using `swap`, and
+    /// returns the handle map along with the number of remaining items. This does not update the
+    /// handles stored in the mesh, and does not truncate storage.
+    fn compact<H: MeshItemHandle>(&mut self, swap: fn(&mut Mesh, H, H)) -> (HandleMap<H>, Size) {
+        let len = H::len(self);
+        let mut map = HandleMap::identity(len);
+        for i in 0..len {
+            let h = H::from_index(i);
+            if self.is_deleted(h) {
+                map.set(h, H::new());
+            }
+        }
+
+        // Invariant: items in `0..i` are kept and those in `j..len` are deleted.
+        let (mut i, mut j): (Index, Index) = (0, len);
+        loop {
+            while i < j && !self.is_deleted(H::from_index(i)) {
+                i += 1;
+            }
+            while i < j && self.is_deleted(H::from_index(j - 1)) {
+                j -= 1;
+            }
+            if i >= j {
+                break;
+            }
+            let (h_deleted, h_kept) = (H::from_index(i), H::from_index(j - 1));
+            swap(self, h_deleted, h_kept);
+            map.set(h_kept, h_deleted);
+            i += 1;
+            j -= 1;
+        }
+        (map, i)
+    }
+
+    /// Swaps two items of the same type along with their properties.
+    fn swap_items<H: MeshItemHandle>(&mut self, h0: H, h1: H) {
+        let (items, props) = H::items_props_mut(self);
+        items.swap(h0.index_us(), h1.index_us());
+        props.swap_all(h0, h1);
+    }
+
+    /// Swaps two edges along with their edge and halfedge properties.
+    fn swap_edges(&mut self, eh0: EdgeHandle, eh1: EdgeHandle) {
+        self.swap_items(eh0, eh1);
+        for i in 0..2 {
+            let hh0 = self.edge_halfedge(eh0, i);
+            let hh1 = self.edge_halfedge(eh1, i);
+            self.h_props.swap_all(hh0, hh1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mesh::item_handle::{FaceHandle, HalfedgeHandle, VertexHandle};
+    use crate::mesh::test_util::{assert_consistent, fan};
+    use crate::property::{Handle, Index};
+
+    #[test]
+    fn no_garbage() {
+        let (mut mesh, _, _) = fan(4);
+        mesh.request_vertex_status();
+        mesh.request_edge_status();
+        mesh.request_face_status();
+        let map = mesh.garbage_collection();
+        assert_eq!(mesh.vertices().len(), 5);
+        assert_eq!(mesh.edges().len(), 8);
+        assert_eq!(mesh.faces().len(), 4);
+        assert_eq!(
+            map.vertices.as_slice(),
+            &(0..5).map(VertexHandle::from_index).collect::<Vec<_>>()[..]
+        );
+        assert_eq!(map.halfedges.len(), 16);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn collect_deleted() {
+        let (mut mesh, v, f) = fan(4);
+        mesh.request_vertex_status();
+        mesh.request_edge_status();
+        mesh.request_face_status();
+        mesh.delete_vertex(v[1], true).unwrap();
+        mesh.delete_face(f[1], true).unwrap();
+        let map = mesh.garbage_collection();
+
+        // Only the face (v0, v3, v4) is left.
+        assert_eq!(mesh.vertices().len(), 3);
+        assert_eq!(mesh.edges().len(), 3);
+        assert_eq!(mesh.halfedges().len(), 6);
+        assert_eq!(mesh.faces().len(), 1);
+        assert!(mesh
+            .get_vertex_status()
+            .unwrap()
+            .storage
+            .iter()
+            .all(|s| !s.deleted()));
+        assert_consistent(&mesh);
+
+        let mut vhs = v.clone();
+        map.vertices.update(&mut vhs);
+        assert!(!vhs[1].is_valid() && !vhs[2].is_valid());
+        let points = [vhs[0], vhs[3], vhs[4]]
+            .iter()
+            .map(|&vh| mesh.point(vh).x)
+            .collect::<Vec<_>>();
+        assert_eq!(points, vec![0.0, 3.0, 4.0]);
+
+        let fh = map.faces.get(f[2]);
+        assert_eq!(fh, FaceHandle::from_index(0));
+        assert_eq!(
+            mesh.fv_ccw_iter(fh).collect::<Vec<_>>(),
+            vec![vhs[0], vhs[3], vhs[4]]
+        );
+        assert!(!map.faces.get(f[0]).is_valid());
+        assert!(!map.faces.get(FaceHandle::new()).is_valid());
+    }
+
+    #[test]
+    fn collect_halfedge_status() {
+        let (mut mesh, v, f) = fan(4);
+        mesh.request_vertex_status();
+        mesh.request_edge_status();
+        mesh.request_face_status();
+        mesh.request_halfedge_status();
+        let hh = mesh.find_halfedge(v[0], v[3]);
+        mesh.get_halfedge_status_mut().unwrap()[hh].set_selected(true);
+        mesh.delete_face(f[0], true).unwrap();
+        let map = mesh.garbage_collection();
+        assert_consistent(&mesh);
+
+        let status = mesh.get_halfedge_status().unwrap();
+        assert_eq!(status.storage.len(), 2 * mesh.edges().len() as usize);
+        assert!(status.storage.iter().all(|s| !s.deleted()));
+        let selected: Vec<_> = mesh
+            .halfedges()
+            .iter()
+            .filter(|&hh| status[hh].selected())
+            .collect();
+        assert_eq!(selected, vec![map.halfedges.get(hh)]);
+    }
+
+    #[test]
+    fn properties_follow_items() {
+        let (mut mesh, v, f) = fan(4);
+        mesh.request_vertex_status();
+        mesh.request_edge_status();
+        mesh.request_face_status();
+        let v_prop = mesh.v_props_mut().add::<u32>(Some("index".into()));
+        let h_prop = mesh.h_props_mut().add::<u32>(Some("index".into()));
+        for i in 0..5 {
+            mesh.v_props_mut().get_mut(v_prop).unwrap()[VertexHandle::from_index(i)] = i;
+        }
+        for i in 0..16 {
+            mesh.h_props_mut().get_mut(h_prop).unwrap()[HalfedgeHandle::from_index(i)] = i;
+        }
+        mesh.delete_face(f[0], true).unwrap();
+        mesh.delete_vertex(v[4], false).unwrap();
+        let map = mesh.garbage_collection();
+        assert_consistent(&mesh);
+
+        let v_list = mesh.v_props().get(v_prop).unwrap();
+        for (old, &new) in map.vertices.as_slice().iter().enumerate() {
+            if new.is_valid() {
+                assert_eq!(v_list[new], old as Index);
+            }
+        }
+        let h_list = mesh.h_props().get(h_prop).unwrap();
+        for (old, &new) in map.halfedges.as_slice().iter().enumerate() {
+            if new.is_valid() {
+                assert_eq!(h_list[new], old as Index);
+            }
+        }
+        assert_eq!(h_list.storage.len(), 2 * mesh.edges().len() as usize);
+    }
+}
@@ -0,0 +1,92 @@
+//! Maps from old to new item handles, returned by mesh operations that renumber items.
+
+use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::property::{Handle, Size};
+
+/// Maps each old handle of one item type to its new handle. Items that no longer exist map to
+/// the invalid handle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandleMap<H: Handle> {
+    map: Vec<H>,
+}
+
+impl<H: Handle> HandleMap<H> {
+    /// Maps each of the `len` handles to itself.
+    pub(crate) fn identity(len: Size) -> Self {
+        HandleMap {
+            map: (0..len).map(H::from_index).collect(),
+        }
+    }
+
+    /// Maps the handle with index `i` to `map[i]`.
+    pub(crate) fn from_vec(map: Vec<H>) -> Self {
+        HandleMap { map }
+    }
+
+    /// Sets the new handle for the old handle `old`.
+    pub(crate) fn set(&mut self, old: H, new: H) {
+        self.map[old.index_us()] = new;
+    }
+
+    /// Number of old handles.
+    pub fn len(&self) -> Size {
+        self.map.len() as Size
+    }
+
+    /// Whether there are no old handles.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// The new handle for the old handle `old`. This is invalid if `old` is invalid, out of range,
+    /// or its item was removed.
+    pub fn get(&self, old: H) -> H {
+        self.map.get(old.index_us()).cloned().unwrap_or_default()
+    }
+
+    /// Replaces each handle in `handles` by its new handle. See `get()`.
+    pub fn update(&self, handles: &mut [H]) {
+        for h in handles.iter_mut() {
+            *h = self.get(*h);
+        }
+    }
+
+    /// The new handles indexed by the old handles' indices.
+    pub fn as_slice(&self) -> &[H] {
+        &self.map
+    }
+}
+
+/// Old-to-new handle maps for each item type, returned by mesh operations that renumber items of
+/// all types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemHandleMaps {
+    /// Vertex handle map.
+    pub vertices: HandleMap<VertexHandle>,
+    /// Halfedge handle map.
+    pub halfedges: HandleMap<HalfedgeHandle>,
+    /// Edge handle map.
+    pub edges: HandleMap<EdgeHandle>,
+    /// Face handle map.
+    pub faces: HandleMap<FaceHandle>,
+}
+
+/// Handle maps returned by `Mesh::garbage_collection()`.
+pub type GarbageCollectionMap = ItemHandleMaps;
+
+/// Maps from the handles of the appended mesh to the new handles in the target mesh, returned by
+/// `Mesh::append()`.
+pub type AppendMap = ItemHandleMaps;
+
+/// Maps from the handles of a mesh to those in a submesh extracted from it, returned by
+/// `Mesh::extract()`. Items not in the submesh map to the invalid handle.
+pub type ExtractMap = ItemHandleMaps;
+
+/// Old-to-new handle maps for the vertices and faces, returned by `Mesh::reorder_spatially()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReorderMap {
+    /// Vertex handle map.
+    pub vertices: HandleMap<VertexHandle>,
+    /// Face handle map.
+    pub faces: HandleMap<FaceHandle>,
+}
@@ -0,0 +1,586 @@
+//! Hole filling, which closes a boundary loop with new triangles.
+//!
+//! The triangulation strategies follow P. Liepa, "Filling Holes in Meshes", SGP 2003.
+use std::f64::consts::PI;
+
+use crate::geometry::vector::Vec3;
+use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::triangulate::newell_normal;
+use crate::mesh::Mesh;
+use crate::property::{Handle, Index};
+
+/// Strategy for `Mesh::fill_hole()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HoleFilling {
+    /// Triangulates the boundary loop without adding vertices, minimizing the total area.
+    MinArea,
+    /// Triangulates the boundary loop without adding vertices, minimizing the maximum dihedral
+    /// angle between adjacent triangles, including the faces around the hole, and then the total
+    /// area.
+    MinDihedral,
+    /// Adds a vertex at the centroid of the boundary loop and connects it to the loop with a fan
+    /// of triangles.
+    CentralVertex {
+        /// Whether to split the triangles until their size matches the edge lengths along the
+        /// boundary, flipping edges to keep the triangulation Delaunay.
+        refine: bool,
+        /// Number of iterations of umbrella smoothing applied to the new vertices, which moves
+        /// each to the centroid of its neighbors.
+        fairing_iterations: usize,
+    },
+}
+
+/// Maximum number of refinement passes of `HoleFilling::CentralVertex`. Each pass splits the
+/// triangles that are too large, which shrinks them by a factor of about `sqrt(3)`, so this
+/// bounds the refinement of holes much larger than their boundary edges, and near boundary
+/// vertices whose edges have no length.
+const MAX_REFINE_PASSES: usize = 32;
+
+/// Cost of a triangulation for `HoleFilling::MinDihedral` and `HoleFilling::MinArea`, compared
+/// lexicographically.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+struct Weight {
+    max_dihedral: f64,
+    area: f64,
+}
+
+impl Weight {
+    const ZERO: Weight = Weight {
+        max_dihedral: 0.0,
+        area: 0.0,
+    };
+    const INFINITE: Weight = Weight {
+        max_dihedral: f64::INFINITY,
+        area: f64::INFINITY,
+    };
+}
+
+/// Angle between two face normals, which is `PI` if either is degenerate.
+fn dihedral_angle(n0: Vec3<f64>, n1: Vec3<f64>) -> f64 {
+    let norms = n0.norm() * n1.norm();
+    if norms == 0.0 {
+        PI
+    } else {
+        (n0.dot(&n1) / norms).clamp(-1.0, 1.0).acos()
+    }
+}
+
+/// The angle at `p0` in the triangle `(p0, p1, p2)`.
+fn angle_at(p0: Vec3<f64>, p1: Vec3<f64>, p2: Vec3<f64>) -> f64 {
+    (p1 - p0).angle(&(p2 - p0))
+}
+
+impl Mesh {
+    /// Fills the hole bounded by the boundary loop containing `boundary_hh` with triangles
+    /// according to `strategy`, and marks all new faces TAGGED. New vertices are placed inside
+    /// the hole, and new edges copy the properties of a boundary edge.
+    ///
+    /// Fails without changing the mesh if
+    ///
+    /// - the face status property is missing,
+    /// - `boundary_hh` is not a boundary halfedge,
+    /// - the loop passes through a vertex more than once, or
+    /// - every triangulation without new vertices would duplicate an existing edge.
+    pub fn fill_hole(&mut self, boundary_hh: HalfedgeHandle, strategy: HoleFilling) -> Result<()> {
+        if self.get_face_status().is_none() {
+            return Err(TopologyError::MissingStatus("face"));
+        }
+        if !self.halfedges().is_valid(boundary_hh)
+            || self.is_deleted(self.edge(boundary_hh))
+            || !self.is_boundary_halfedge(boundary_hh)
+        {
+            return Err(TopologyError::HalfedgeNotBoundary(boundary_hh));
+        }
+        let mut hhs = vec![boundary_hh];
+        loop {
+            let hh = self.next_halfedge(*hhs.last().unwrap());
+            if hh == boundary_hh {
+                break;
+            }
+            hhs.push(hh);
+        }
+        let vhs = hhs
+            .iter()
+            .map(|&hh| self.from_vertex(hh))
+            .collect::<Vec<_>>();
+        for (i, &vh) in vhs.iter().enumerate() {
+            if vhs[..i].contains(&vh) {
+                return Err(TopologyError::ComplexVertex(vh));
+            }
+        }
+
+        match strategy {
+            HoleFilling::MinArea | HoleFilling::MinDihedral => {
+                let ears = self.min_weight_triangulation(&hhs, strategy)?;
+                let fh = self.fill_loop(&vhs);
+                self.cut_ears(hhs, &ears);
+                debug_assert!(self.faces().len() as usize - fh.index_us() == vhs.len() - 2);
+            }
+            HoleFilling::CentralVertex {
+                refine,
+                fairing_iterations,
+            } => {
+                let nv = self.vertices().len();
+                let centroid = vhs.iter().map(|&vh| self.point(vh)).sum::<Vec3<f64>>();
+                let vh = self.add_vertex(centroid / vhs.len() as f64);
+                let fh = self.fill_loop(&vhs);
+                self.split_face(fh, vh).unwrap();
+                if refine {
+                    self.refine_fill(&hhs, fh);
+                }
+                self.fair_fill(nv, fairing_iterations);
+            }
+        }
+        Ok(())
+    }
+
+    /// Closes the boundary loop through `vhs` with a single TAGGED face and returns it.
+    fn fill_loop(&mut self, vhs: &[VertexHandle]) -> FaceHandle {
+        let fh = self
+            .add_face(vhs)
+            .expect("a simple boundary loop can always be closed by a face");
+        self.get_face_status_mut().unwrap()[fh].set_tagged(true);
+        fh
+    }
+
+    /// Plans the triangulation of the boundary loop `hhs` with minimum weight by dynamic
+    /// programming, as the sequence of ears for `Mesh::cut_ears()`. Triangles that would
+    /// duplicate existing edges are avoided.
+    fn min_weight_triangulation(
+        &self,
+        hhs: &[HalfedgeHandle],
+        strategy: HoleFilling,
+    ) -> Result<Vec<[usize; 3]>> {
+        let n = hhs.len();
+        let vhs = hhs
+            .iter()
+            .map(|&hh| self.from_vertex(hh))
+            .collect::<Vec<_>>();
+        let p = vhs.iter().map(|&vh| self.point(vh)).collect::<Vec<_>>();
+        let normal = |a: usize, b: usize, c: usize| (p[b] - p[a]).cross(&(p[c] - p[a]));
+        // Normals of the faces across the boundary edges, where edge `i` starts at vertex `i`.
+        let outer = hhs
+            .iter()
+            .map(|&hh| {
+                let fh = self.face(self.opposite_halfedge(hh));
+                if fh.is_valid() {
+                    let points = self.fv_ccw_iter(fh).map(|vh| self.point(vh));
+                    Some(newell_normal(&points.collect::<Vec<_>>()))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // `weights[i][j]` and `splits[i][j]` describe the best triangulation of the polygon
+        // through vertices `i..=j`, whose triangle at the edge `(i, j)` has its tip at the split.
+        let mut weights = vec![vec![Weight::ZERO; n]; n];
+        let mut splits = vec![vec![0; n]; n];
+        let mut blocked = None;
+        for len in 2..n {
+            for i in 0..n - len {
+                let j = i + len;
+                if len < n - 1 && self.find_halfedge(vhs[i], vhs[j]).is_valid() {
+                    weights[i][j] = Weight::INFINITE;
+                    blocked = blocked.or(Some((vhs[i], vhs[j])));
+                    continue;
+                }
+                // The normal of the triangle across the edge `(i, j)` of the sub-polygon, or of
+                // the face across the boundary edge if the sub-polygon is a single edge.
+                let adjacent = |splits: &[Vec<usize>], i: usize, j: usize| {
+                    if j - i > 1 {
+                        Some(normal(i, splits[i][j], j))
+                    } else {
+                        outer[i]
+                    }
+                };
+                let mut best = Weight::INFINITE;
+                #[allow(clippy::needless_range_loop)]
+                for m in i + 1..j {
+                    let (left, right) = (weights[i][m], weights[m][j]);
+                    if left == Weight::INFINITE || right == Weight::INFINITE {
+                        continue;
+                    }
+                    let tri_normal = normal(i, m, j);
+                    let mut max_dihedral = left.max_dihedral.max(right.max_dihedral);
+                    if strategy == HoleFilling::MinDihedral {
+                        let mut neighbors = vec![adjacent(&splits, i, m), adjacent(&splits, m, j)];
+                        if len == n - 1 {
+                            neighbors.push(outer[n - 1]);
+                        }
+                        for neighbor in neighbors.into_iter().flatten() {
+                            max_dihedral = max_dihedral.max(dihedral_angle(tri_normal, neighbor));
+                        }
+                    }
+                    let weight = Weight {
+                        max_dihedral,
+                        area: left.area + right.area + tri_normal.norm() / 2.0,
+                    };
+                    if weight < best {
+                        best = weight;
+                        splits[i][j] = m;
+                    }
+                }
+                weights[i][j] = best;
+            }
+        }
+        if weights[0][n - 1] == Weight::INFINITE {
+            let (v0, v1) = blocked.unwrap();
+            return Err(TopologyError::DuplicateEdge(v0, v1));
+        }
+
+        // Emit the triangles of each sub-polygon before the triangle at its edge, which is then
+        // an ear. The last triangle is left over by `cut_ears()`.
+        let mut ears = Vec::with_capacity(n - 2);
+        let mut stack = vec![(0, n - 1, false)];
+        while let Some((i, j, children_done)) = stack.pop() {
+            if j - i < 2 {
+                continue;
+            }
+            let m = splits[i][j];
+            if children_done {
+                ears.push([i, m, j]);
+            } else {
+                stack.push((i, j, true));
+                stack.push((m, j, false));
+                stack.push((i, m, false));
+            }
+        }
+        ears.pop();
+        Ok(ears)
+    }
+
+    /// Refines the faces from `fh` onwards filling the hole bounded by `hhs`: triangles larger
+    /// than the local edge length, as interpolated from the lengths along the boundary, are split
+    /// at their centroid, and edges are flipped to keep the triangulation Delaunay. Stops after
+    /// `MAX_REFINE_PASSES` passes.
+    fn refine_fill(&mut self, hhs: &[HalfedgeHandle], fh: FaceHandle) {
+        let n = hhs.len();
+        let nf = fh.index();
+        let length = |mesh: &Mesh, hh| {
+            (mesh.point(mesh.to_vertex(hh)) - mesh.point(mesh.from_vertex(hh))).norm()
+        };
+        // Target edge lengths of the vertices, where the new vertices are at the end.
+        let mut sigma = vec![0.0; self.vertices().len() as usize];
+        for (i, &hh) in hhs.iter().enumerate() {
+            let prev_hh = hhs[(i + n - 1) % n];
+            let vh = self.from_vertex(hh);
+            sigma[vh.index_us()] = (length(self, hh) + length(self, prev_hh)) / 2.0;
+        }
+        let boundary_sigma = sigma.iter().sum::<f64>() / n as f64;
+        *sigma.last_mut().unwrap() = boundary_sigma;
+
+        // Each pass triples the faces that are split, so only few passes are usually needed.
+        for _ in 0..MAX_REFINE_PASSES {
+            let mut split_any = false;
+            for i in nf..self.faces().len() {
+                let fh = FaceHandle::from_index(i);
+                let vhs = self.fv_ccw_iter(fh).collect::<Vec<_>>();
+                let centroid = vhs.iter().map(|&vh| self.point(vh)).sum::<Vec3<f64>>() / 3.0;
+                let sigma_c = vhs.iter().map(|vh| sigma[vh.index_us()]).sum::<f64>() / 3.0;
+                let is_large = vhs.iter().all(|&vh| {
+                    let dist = 2f64.sqrt() * (centroid - self.point(vh)).norm();
+                    dist > sigma_c && dist > sigma[vh.index_us()]
+                });
+                if is_large {
+                    let vh = self.add_vertex(centroid);
+                    sigma.push(sigma_c);
+                    self.split_face(fh, vh).unwrap();
+                    split_any = true;
+                }
+            }
+            if !split_any {
+                break;
+            }
+            self.relax_fill(nf);
+        }
+    }
+
+    /// Flips the edges between the faces from index `nf` onwards until they satisfy the Delaunay
+    /// criterion, i.e. the angles opposite to each edge sum to at most `PI`.
+    fn relax_fill(&mut self, nf: Index) {
+        let is_fill = |mesh: &Mesh, hh| {
+            let fh = mesh.face(hh);
+            fh.is_valid() && fh.index() >= nf
+        };
+        let max_passes = self.edges().len();
+        for _ in 0..max_passes {
+            let mut flipped_any = false;
+            for i in 0..self.edges().len() {
+                let eh = EdgeHandle::from_index(i);
+                let (h0, h1) = (self.edge_halfedge(eh, 0), self.edge_halfedge(eh, 1));
+                if !is_fill(self, h0) || !is_fill(self, h1) || !self.is_flip_ok(eh) {
+                    continue;
+                }
+                let opposite_angle = |hh| {
+                    let next = self.next_halfedge(hh);
+                    let tip = self.point(self.to_vertex(next));
+                    let p0 = self.point(self.from_vertex(hh));
+                    let p1 = self.point(self.to_vertex(hh));
+                    angle_at(tip, p0, p1)
+                };
+                if opposite_angle(h0) + opposite_angle(h1) > PI + 1e-9 {
+                    self.flip(eh).unwrap();
+                    flipped_any = true;
+                }
+            }
+            if !flipped_any {
+                break;
+            }
+        }
+    }
+
+    /// Applies `iterations` of umbrella smoothing to the vertices from index `nv` onwards.
+    fn fair_fill(&mut self, nv: Index, iterations: usize) {
+        for _ in 0..iterations {
+            let points = (nv..self.vertices().len())
+                .map(|i| {
+                    let vh = VertexHandle::from_index(i);
+                    let (sum, count) = self
+                        .vv_ccw_iter(vh)
+                        .fold((Vec3::zeros(), 0), |(sum, count), vh| {
+                            (sum + self.point(vh), count + 1)
+                        });
+                    sum / count as f64
+                })
+                .collect::<Vec<_>>();
+            for (i, p) in (nv..).zip(points) {
+                self.set_point(VertexHandle::from_index(i), p);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dihedral_angle, HoleFilling};
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{HalfedgeHandle, VertexHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, grid, mesh_with_vertices, request_status};
+    use crate::mesh::triangulate::newell_normal;
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    /// Grid of `n` by `n` quads with the `k` by `k` quads in its middle deleted, along with a
+    /// halfedge on the boundary of the hole.
+    fn grid_with_hole(n: usize, k: usize) -> (Mesh, HalfedgeHandle) {
+        let (mut mesh, v) = grid(n);
+        request_status(&mut mesh);
+        let start = (n - k) / 2;
+        for i in start..start + k {
+            for j in start..start + k {
+                let hh = mesh.find_halfedge(v[i * (n + 1) + j], v[i * (n + 1) + j + 1]);
+                mesh.delete_face(mesh.face(hh), false).unwrap();
+            }
+        }
+        let corner = v[start * (n + 1) + start];
+        let hh = mesh.find_halfedge(corner, v[start * (n + 1) + start + 1]);
+        (mesh, hh)
+    }
+
+    /// Checks that the mesh has a single boundary loop and that the faces from `nf` onwards are
+    /// all TAGGED, unlike those before.
+    fn assert_filled(mesh: &Mesh, nf: usize) {
+        assert_consistent(mesh);
+        assert_eq!(mesh.boundary_loops().len(), 1);
+        let status = mesh.get_face_status().unwrap();
+        for fh in mesh.faces().iter() {
+            assert_eq!(status[fh].tagged(), fh.index_us() >= nf, "{}", fh);
+        }
+    }
+
+    /// The numbers of vertices and edges, and the vertices of each face.
+    fn snapshot(mesh: &Mesh) -> (usize, usize, Vec<Vec<VertexHandle>>) {
+        let fvs = mesh
+            .faces()
+            .iter()
+            .map(|fh| mesh.fv_ccw_iter(fh).collect())
+            .collect();
+        (
+            mesh.vertices().len() as usize,
+            mesh.edges().len() as usize,
+            fvs,
+        )
+    }
+
+    #[test]
+    fn fill_min_area() {
+        let (mut mesh, hh) = grid_with_hole(3, 1);
+        mesh.fill_hole(hh, HoleFilling::MinArea).unwrap();
+        assert_filled(&mesh, 9);
+        assert_eq!(mesh.faces().iter().count(), 10);
+
+        // A 2x2 hole has 8 boundary vertices, 4 of which are in the middle of a side. Triangles
+        // with three collinear vertices have no area, so the minimum area is that of the hole.
+        let (mut mesh, hh) = grid_with_hole(4, 2);
+        mesh.fill_hole(hh, HoleFilling::MinArea).unwrap();
+        assert_filled(&mesh, 16);
+        assert_eq!(mesh.faces().iter().count(), 12 + 6);
+    }
+
+    /// Maximum dihedral angle at the edges of the faces from index `nf` onwards, and the total
+    /// area of these faces.
+    fn fill_quality(mesh: &Mesh, nf: usize) -> (f64, f64) {
+        let normal = |fh| {
+            let points = mesh.fv_ccw_iter(fh).map(|vh| mesh.point(vh));
+            newell_normal(&points.collect::<Vec<_>>())
+        };
+        let mut max_dihedral = 0.0f64;
+        let mut area = 0.0;
+        for fh in mesh.faces().iter().filter(|fh| fh.index_us() >= nf) {
+            area += normal(fh).norm() / 2.0;
+            for other in mesh.ff_ccw_iter(fh) {
+                max_dihedral = max_dihedral.max(dihedral_angle(normal(fh), normal(other)));
+            }
+        }
+        (max_dihedral, area)
+    }
+
+    #[test]
+    fn fill_min_dihedral() {
+        // Hexagonal hole in a crown of triangles, whose ring zigzags up and down.
+        let heights = [0.0, 0.6, 2.0, 0.4, 1.8, 0.2];
+        let mut mesh = Mesh::new();
+        request_status(&mut mesh);
+        let points = |r: f64, offset: f64| {
+            (0..6)
+                .map(|i| {
+                    let angle = (i as f64 + offset) * std::f64::consts::PI / 3.0;
+                    Vec3::new(r * angle.cos(), r * angle.sin(), r * heights[i])
+                })
+                .collect::<Vec<_>>()
+        };
+        let ring = points(1.0, 0.0)
+            .into_iter()
+            .map(|p| mesh.add_vertex(p))
+            .collect::<Vec<_>>();
+        let outer = points(2.0, 0.5)
+            .into_iter()
+            .map(|p| mesh.add_vertex(p))
+            .collect::<Vec<_>>();
+        for i in 0..6 {
+            mesh.add_face(&[ring[(i + 1) % 6], ring[i], outer[i]])
+                .unwrap();
+        }
+        let hh = mesh.find_halfedge(ring[0], ring[1]);
+        assert!(mesh.is_boundary(hh));
+
+        let mut by_area = mesh.clone();
+        by_area.fill_hole(hh, HoleFilling::MinArea).unwrap();
+        assert_filled(&by_area, 6);
+        mesh.fill_hole(hh, HoleFilling::MinDihedral).unwrap();
+        assert_filled(&mesh, 6);
+        assert_eq!(mesh.faces().iter().count(), 6 + 4);
+
+        // Each strategy is optimal for its own criterion, and they differ here.
+        let (dihedral, area) = fill_quality(&mesh, 6);
+        let (by_area_dihedral, by_area_area) = fill_quality(&by_area, 6);
+        assert!(dihedral <= by_area_dihedral + 1e-9);
+        assert!(by_area_area <= area + 1e-9);
+        assert!(dihedral < by_area_dihedral && by_area_area < area);
+    }
+
+    #[test]
+    fn fill_central_vertex() {
+        let (mut mesh, hh) = grid_with_hole(4, 2);
+        let strategy = HoleFilling::CentralVertex {
+            refine: false,
+            fairing_iterations: 0,
+        };
+        mesh.fill_hole(hh, strategy).unwrap();
+        assert_filled(&mesh, 16);
+        assert_eq!(mesh.faces().iter().count(), 12 + 8);
+        let vh = mesh.vertices().iter().next_back().unwrap();
+        assert_eq!(mesh.point(vh), Vec3::new(2.0, 2.0, 0.0));
+        assert_eq!(mesh.vertex_valence(vh), 8);
+    }
+
+    #[test]
+    fn fill_refined() {
+        let (mut mesh, hh) = grid_with_hole(8, 6);
+        let nv = mesh.vertices().len();
+        let strategy = HoleFilling::CentralVertex {
+            refine: true,
+            fairing_iterations: 10,
+        };
+        mesh.fill_hole(hh, strategy).unwrap();
+        assert_filled(&mesh, 64);
+        assert!(mesh.vertices().len() > nv + 1);
+        for vh in mesh.vertices().iter().skip(nv as usize) {
+            let p = mesh.point(vh);
+            assert!(p.z.abs() < 1e-9);
+            assert!(p.x > 1.0 && p.x < 7.0 && p.y > 1.0 && p.y < 7.0);
+        }
+    }
+
+    #[test]
+    fn fill_refined_zero_length_edges() {
+        // The vertex in the corner of the hole gets a target edge length of 0, as its neighbors
+        // along the boundary are moved onto it.
+        let (mut mesh, hh) = grid_with_hole(4, 2);
+        let corner = mesh.from_vertex(hh);
+        let p = mesh.point(corner);
+        let prev = mesh.from_vertex(mesh.prev_halfedge(hh));
+        mesh.set_point(mesh.to_vertex(hh), p);
+        mesh.set_point(prev, p);
+        let strategy = HoleFilling::CentralVertex {
+            refine: true,
+            fairing_iterations: 0,
+        };
+        mesh.fill_hole(hh, strategy).unwrap();
+        assert_filled(&mesh, 16);
+    }
+
+    #[test]
+    fn fill_hole_fails() {
+        let (mut mesh, hh) = grid_with_hole(3, 1);
+        let opp = mesh.opposite_halfedge(hh);
+        assert_eq!(
+            mesh.fill_hole(opp, HoleFilling::MinArea),
+            Err(TopologyError::HalfedgeNotBoundary(opp))
+        );
+        mesh.release_face_status();
+        assert_eq!(
+            mesh.fill_hole(hh, HoleFilling::MinArea),
+            Err(TopologyError::MissingStatus("face"))
+        );
+
+        // Two triangles sharing only vertex 0, whose single boundary loop passes through it
+        // twice.
+        let (mut mesh, v) = mesh_with_vertices(5);
+        request_status(&mut mesh);
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        mesh.add_face(&[v[0], v[3], v[4]]).unwrap();
+        let before = snapshot(&mesh);
+        let hh = mesh.find_halfedge(v[1], v[0]);
+        assert_eq!(
+            mesh.fill_hole(hh, HoleFilling::MinArea),
+            Err(TopologyError::ComplexVertex(v[0]))
+        );
+        assert_eq!(snapshot(&mesh), before);
+
+        // The 7-vertex torus, in which every two vertices are connected by an edge. Deleting a
+        // vertex leaves a hole whose diagonals all exist.
+        let (mut mesh, v) = mesh_with_vertices(7);
+        request_status(&mut mesh);
+        for i in 0..7 {
+            mesh.add_face(&[v[i], v[(i + 1) % 7], v[(i + 3) % 7]])
+                .unwrap();
+            mesh.add_face(&[v[i], v[(i + 3) % 7], v[(i + 2) % 7]])
+                .unwrap();
+        }
+        assert_eq!(mesh.edges().len(), 21);
+        mesh.delete_vertex(v[0], false).unwrap();
+        let before = snapshot(&mesh);
+        let hh = mesh.boundary_halfedges().next().unwrap();
+        for &strategy in &[HoleFilling::MinArea, HoleFilling::MinDihedral] {
+            assert!(matches!(
+                mesh.fill_hole(hh, strategy),
+                Err(TopologyError::DuplicateEdge(_, _))
+            ));
+            assert_eq!(snapshot(&mesh), before);
+        }
+    }
+}
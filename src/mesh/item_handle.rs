@@ -72,11 +72,14 @@ pub trait MeshItemHandle: ItemHandle {
 
     /// Gets the status property.
     fn status_prop(mesh: &Mesh) -> Option<&PropertyList<Status, Self>>;
+
+    /// Gets the status property mutably.
+    fn status_prop_mut(mesh: &mut Mesh) -> Option<&mut PropertyList<Status, Self>>;
 }
 
 macro_rules! impl_to_items {
     ($Item:ty, $ContainerItem:ty, $Handle:ty, $prefix:expr, $item_field:ident, $prop_field:ident,
-     $get_status:ident,
+     $get_status:ident, $get_status_mut:ident,
      ($vec:ident, $handle:ident) -> {
          fn num_items: $num_items:expr,
          fn get: $get:expr,
@@ -114,13 +117,18 @@ macro_rules! impl_to_items {
             fn status_prop(mesh: &Mesh) -> Option<&PropertyList<Status, Self>> {
                 mesh.$get_status()
             }
+
+            fn status_prop_mut(mesh: &mut Mesh) -> Option<&mut PropertyList<Status, Self>> {
+                mesh.$get_status_mut()
+            }
         }
     };
 
     ($Item:ty, $ContainerItem:ty, $Handle:ty, $prefix:expr, $item_field:ident, $prop_field:ident,
-     $get_status:ident) => {
+     $get_status:ident, $get_status_mut:ident) => {
         impl_to_items!(
             $Item, $ContainerItem, $Handle, $prefix, $item_field, $prop_field, $get_status,
+            $get_status_mut,
             (vec, handle) -> {
                 fn num_items: vec.len(),
                 fn get:       vec.get(handle.index_us()),
@@ -137,7 +145,8 @@ impl_to_items!(
     "v:",
     vertices,
     v_props,
-    get_vertex_status
+    get_vertex_status,
+    get_vertex_status_mut
 );
 impl_to_items!(
     Edge,
@@ -146,7 +155,8 @@ impl_to_items!(
     "e:",
     edges,
     e_props,
-    get_edge_status
+    get_edge_status,
+    get_edge_status_mut
 );
 impl_to_items!(
     Face,
@@ -155,13 +165,15 @@ impl_to_items!(
     "f:",
     faces,
     f_props,
-    get_face_status
+    get_face_status,
+    get_face_status_mut
 );
 impl_to_items!(Halfedge,   Edge, HalfedgeHandle, "h:",    edges, h_props, get_halfedge_status,
+get_halfedge_status_mut,
 // Halfedges are stored within edges.
 (vec, handle) -> {
     fn num_items: {
-        debug_assert!(vec.len() <= usize::max_value() / 2);
+        debug_assert!(vec.len() <= usize::MAX / 2);
         vec.len() * 2
     },
     fn get: {
@@ -173,3 +185,52 @@ impl_to_items!(Halfedge,   Edge, HalfedgeHandle, "h:",    edges, h_props, get_ha
         vec.get_mut(index / 2).map(|edge| &mut edge.0[index % 2])
     },
 });
+
+/// Handles to mesh items that store a halfedge anchoring them in the mesh: vertices (an outgoing
+/// halfedge) and faces (a bounding halfedge). See `Mesh::halfedge()`.
+pub trait HalfedgeAnchorHandle: MeshItemHandle {
+    /// Gets the anchoring halfedge of the item.
+    fn anchor_halfedge(mesh: &Mesh, handle: Self) -> HalfedgeHandle;
+}
+
+impl HalfedgeAnchorHandle for VertexHandle {
+    fn anchor_halfedge(mesh: &Mesh, handle: Self) -> HalfedgeHandle {
+        mesh.vertex_halfedge(handle)
+    }
+}
+
+impl HalfedgeAnchorHandle for FaceHandle {
+    fn anchor_halfedge(mesh: &Mesh, handle: Self) -> HalfedgeHandle {
+        mesh.face_halfedge(handle)
+    }
+}
+
+/// Handles to mesh items that may lie on the mesh boundary. See `Mesh::is_boundary()`.
+pub trait BoundaryItemHandle: MeshItemHandle {
+    /// Whether the item lies on the boundary.
+    fn is_boundary(mesh: &Mesh, handle: Self) -> bool;
+}
+
+impl BoundaryItemHandle for VertexHandle {
+    fn is_boundary(mesh: &Mesh, handle: Self) -> bool {
+        mesh.is_boundary_vertex(handle)
+    }
+}
+
+impl BoundaryItemHandle for HalfedgeHandle {
+    fn is_boundary(mesh: &Mesh, handle: Self) -> bool {
+        mesh.is_boundary_halfedge(handle)
+    }
+}
+
+impl BoundaryItemHandle for EdgeHandle {
+    fn is_boundary(mesh: &Mesh, handle: Self) -> bool {
+        mesh.is_boundary_edge(handle)
+    }
+}
+
+impl BoundaryItemHandle for FaceHandle {
+    fn is_boundary(mesh: &Mesh, handle: Self) -> bool {
+        mesh.is_boundary_face(handle)
+    }
+}
@@ -4,9 +4,11 @@
 use crate::mesh::item_handle::{
     EdgeHandle, FaceHandle, HalfedgeHandle, MeshItemHandle, VertexHandle,
 };
+use crate::mesh::iter::ItemIter;
 use crate::mesh::prop::{Props, PropsMut};
-use crate::property::PropertyContainer;
+use crate::mesh::status::Status;
 use crate::property::Size;
+use crate::property::{PropertyContainer, PropertyList};
 
 ////////////////////////////////////////////////////////////
 // Half-edge data structure connectivity fields
@@ -58,18 +60,42 @@ pub struct Items<'a, H: MeshItemHandle> {
     items: &'a ContainerSlice<H>,
     /// Item properties.
     props: &'a PropertyContainer<H>,
+    /// Item status property, if any, for skipping items while iterating.
+    status: Option<&'a PropertyList<Status, H>>,
     _marker: ::std::marker::PhantomData<H>,
 }
 
 impl<'a, H: MeshItemHandle> Items<'a, H> {
     /// Instantiates an item + property interface struct.
-    pub(crate) fn new(items: &'a ContainerSlice<H>, props: &'a PropertyContainer<H>) -> Self {
+    pub(crate) fn new(
+        items: &'a ContainerSlice<H>,
+        props: &'a PropertyContainer<H>,
+        status: Option<&'a PropertyList<Status, H>>,
+    ) -> Self {
         Items {
             items,
             props,
+            status,
             _marker: ::std::marker::PhantomData,
         }
     }
+
+    /// Iterates through the handles of all items, skipping those with DELETED or HIDDEN status
+    /// if the mesh stores status for this item type.
+    pub fn iter(&self) -> ItemIter<'a, H> {
+        ItemIter::new(self.len(), self.status, /* skip= */ true)
+    }
+
+    /// Iterates through the handles of all items, including DELETED and HIDDEN ones.
+    pub fn iter_all(&self) -> ItemIter<'a, H> {
+        ItemIter::new(self.len(), self.status, /* skip= */ false)
+    }
+
+    /// Iterates backwards through the handles of all items, skipping those with DELETED or HIDDEN
+    /// status if the mesh stores status for this item type. Same as `self.iter().rev()`.
+    pub fn rev(&self) -> ::std::iter::Rev<ItemIter<'a, H>> {
+        self.iter().rev()
+    }
 }
 
 /// Manages immutable and mutable operations on the list of a particular mesh item type.
@@ -127,7 +153,7 @@ macro_rules! impl_items {
 
             #[doc = "Number of items of the item type."]
             pub fn len(&self) -> Size {
-                debug_assert!(self.len_us() <= Size::max_value() as usize);
+                debug_assert!(self.len_us() <= Size::MAX as usize);
                 self.len_us() as Size
             }
 
@@ -175,6 +201,11 @@ macro_rules! impl_items {
             pub fn empty(&self) -> bool {
                 self.len_us() == 0
             }
+
+            #[doc = "Returns true when there are no items of the mesh item type. Same as `empty()`."]
+            pub fn is_empty(&self) -> bool {
+                self.empty()
+            }
         }
     };
 }
@@ -189,13 +220,13 @@ where
 {
     /// Gets the mutable item at the handle.
     pub fn get_mut(&mut self, handle: H) -> Option<&mut H::Item> {
-        <H as MeshItemHandle>::get_mut(&mut self.items, handle)
+        <H as MeshItemHandle>::get_mut(self.items, handle)
     }
 
     /// Returns the mutable properties container associated with the mesh item type.
     pub fn props_mut(&mut self) -> PropsMut<'_, H> {
         let len = self.len();
-        PropsMut::new(&mut self.props, len)
+        PropsMut::new(self.props, len)
     }
 
     /// Returns the mutable properties container associated with the mesh item type.
@@ -218,9 +249,9 @@ where
     pub fn append(&mut self) -> H {
         let old_len = self.items.len();
         assert!(
-            old_len < Size::max_value() as usize,
+            old_len < Size::MAX as usize,
             "Cannot add more than {} items. Already have {}.",
-            Size::max_value(),
+            Size::MAX,
             old_len
         );
         let old_len = old_len as Size;
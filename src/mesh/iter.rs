@@ -1,9 +1,12 @@
-//! Iterators to enumerate all items in the mesh, skipping DELETED and HIDDEN items.
+//! Iterators to enumerate all items in the mesh, skipping DELETED and HIDDEN items, and
+//! circulators to enumerate the items in the neighborhood of a vertex or face.
 
-use crate::mesh::item_handle::MeshItemHandle;
+use crate::mesh::item_handle::{
+    EdgeHandle, FaceHandle, HalfedgeHandle, MeshItemHandle, VertexHandle,
+};
 use crate::mesh::status::Status;
 use crate::mesh::Mesh;
-use crate::property::PropertyList;
+use crate::property::{Handle, Index, PropertyList, Size};
 
 struct IterBase<'a, H: MeshItemHandle> {
     mesh: &'a Mesh,
@@ -183,6 +186,272 @@ impl<'a, H: MeshItemHandle> Iterator for BwdIter<'a, H> {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Range iterators
+
+/// Double-ended iterator through a range of items of one type, optionally skipping DELETED and
+/// HIDDEN items. These are created by `Items::iter()`, `Items::iter_all()`, and `Items::rev()`,
+/// e.g. `mesh.vertices().iter()`.
+///
+/// `size_hint()` is exact, but counts the remaining items on each call when items may be skipped,
+/// so it takes linear time then.
+pub struct ItemIter<'a, H: MeshItemHandle> {
+    /// Index of the next item from the front.
+    front: Index,
+    /// One past the index of the next item from the back.
+    back: Index,
+    status_prop: Option<&'a PropertyList<Status, H>>,
+    skip_bits: Status,
+}
+
+// Manually implement `Copy`, `Clone` due to https://github.com/rust-lang/rust/issues/32872.
+impl<'a, H: MeshItemHandle> Copy for ItemIter<'a, H> {}
+impl<'a, H: MeshItemHandle> Clone for ItemIter<'a, H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, H: MeshItemHandle> ::std::fmt::Debug for ItemIter<'a, H> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        write!(
+            f,
+            "ItemIter(front={}, back={}, skip_bits={:?})",
+            self.front, self.back, self.skip_bits
+        )
+    }
+}
+
+impl<'a, H: MeshItemHandle> ItemIter<'a, H> {
+    /// Iterates through the `len` items. If `skip` is true and `status_prop` is provided, then
+    /// the iterator skips all elements with DELETED or HIDDEN status.
+    pub(crate) fn new(
+        len: Size,
+        status_prop: Option<&'a PropertyList<Status, H>>,
+        skip: bool,
+    ) -> Self {
+        let skip_bits = if skip {
+            Status::DELETED | Status::HIDDEN
+        } else {
+            Status::empty()
+        };
+        ItemIter {
+            front: 0,
+            back: len,
+            status_prop,
+            skip_bits,
+        }
+    }
+
+    fn should_skip(&self, h: H) -> bool {
+        // `iter_all()` never reads the status, and items beyond the end of a short status list
+        // are not skipped, since `Mesh::check_topology()` reports such lists.
+        !self.skip_bits.is_empty()
+            && self
+                .status_prop
+                .and_then(|prop| prop.storage.get(h.index_us()))
+                .is_some_and(|status| !(*status & self.skip_bits).is_empty())
+    }
+}
+
+impl<'a, H: MeshItemHandle> Iterator for ItemIter<'a, H> {
+    type Item = H;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            let h = H::from_index(self.front);
+            self.front += 1;
+            if !self.should_skip(h) {
+                return Some(h);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = if self.skip_bits.is_empty() || self.status_prop.is_none() {
+            (self.back - self.front) as usize
+        } else {
+            (self.front..self.back)
+                .filter(|&i| !self.should_skip(H::from_index(i)))
+                .count()
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, H: MeshItemHandle> DoubleEndedIterator for ItemIter<'a, H> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            self.back -= 1;
+            let h = H::from_index(self.back);
+            if !self.should_skip(h) {
+                return Some(h);
+            }
+        }
+        None
+    }
+}
+
+impl<'a, H: MeshItemHandle> ExactSizeIterator for ItemIter<'a, H> {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Circulators
+
+/// Lazily enumerates items around a vertex or face by walking a cycle of halfedges.
+///
+/// Around a vertex, the cycle consists of the outgoing halfedges. Around a face, it consists of
+/// the halfedges bounding the face. Each halfedge is mapped to the enumerated item, and
+/// halfedges for which there is no such item (e.g. boundary halfedges without a face) are
+/// skipped.
+///
+/// These are created by `Mesh`'s circulator methods, e.g. `vv_ccw_iter()` or `fv_cw_iter()`.
+/// The mesh connectivity must not be inconsistent, or else the circulator may not terminate.
+pub struct Circulator<'a, T> {
+    mesh: &'a Mesh,
+    start: HalfedgeHandle,
+    hh: HalfedgeHandle,
+    advance: fn(&Mesh, HalfedgeHandle) -> HalfedgeHandle,
+    map: fn(&Mesh, HalfedgeHandle) -> Option<T>,
+}
+
+// Manually implement `Copy`, `Clone` due to https://github.com/rust-lang/rust/issues/32872.
+impl<'a, T> Copy for Circulator<'a, T> {}
+impl<'a, T> Clone for Circulator<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> ::std::fmt::Debug for Circulator<'a, T> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        write!(f, "Circulator(start={:?}, hh={:?})", self.start, self.hh)
+    }
+}
+
+impl<'a, T> Circulator<'a, T> {
+    /// Circulates starting at `start` (which may be invalid for an empty circulation), stepping
+    /// with `advance` until reaching `start` again.
+    fn new(
+        mesh: &'a Mesh,
+        start: HalfedgeHandle,
+        advance: fn(&Mesh, HalfedgeHandle) -> HalfedgeHandle,
+        map: fn(&Mesh, HalfedgeHandle) -> Option<T>,
+    ) -> Self {
+        Circulator {
+            mesh,
+            start,
+            hh: start,
+            advance,
+            map,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Circulator<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.hh.is_valid() {
+            let hh = self.hh;
+            self.hh = (self.advance)(self.mesh, hh);
+            if self.hh == self.start {
+                self.hh.invalidate();
+            }
+            let item = (self.map)(self.mesh, hh);
+            if item.is_some() {
+                return item;
+            }
+        }
+        None
+    }
+}
+
+/// Circulator through the vertices adjacent to a vertex.
+pub type VertexVertexIter<'a> = Circulator<'a, VertexHandle>;
+/// Circulator through the halfedges starting (outgoing) or ending (incoming) at a vertex.
+pub type VertexHalfedgeIter<'a> = Circulator<'a, HalfedgeHandle>;
+/// Circulator through the edges incident to a vertex.
+pub type VertexEdgeIter<'a> = Circulator<'a, EdgeHandle>;
+/// Circulator through the faces incident to a vertex.
+pub type VertexFaceIter<'a> = Circulator<'a, FaceHandle>;
+/// Circulator through the vertices of a face.
+pub type FaceVertexIter<'a> = Circulator<'a, VertexHandle>;
+/// Circulator through the halfedges of a face.
+pub type FaceHalfedgeIter<'a> = Circulator<'a, HalfedgeHandle>;
+/// Circulator through the edges of a face.
+pub type FaceEdgeIter<'a> = Circulator<'a, EdgeHandle>;
+/// Circulator through the faces adjacent to a face across its edges.
+pub type FaceFaceIter<'a> = Circulator<'a, FaceHandle>;
+
+// Maps from the circulated halfedge to the enumerated item.
+fn hh_outgoing(_: &Mesh, hh: HalfedgeHandle) -> Option<HalfedgeHandle> {
+    Some(hh)
+}
+fn hh_incoming(mesh: &Mesh, hh: HalfedgeHandle) -> Option<HalfedgeHandle> {
+    Some(mesh.opposite_halfedge(hh))
+}
+fn hh_to_vertex(mesh: &Mesh, hh: HalfedgeHandle) -> Option<VertexHandle> {
+    Some(mesh.to_vertex(hh))
+}
+fn hh_edge(mesh: &Mesh, hh: HalfedgeHandle) -> Option<EdgeHandle> {
+    Some(mesh.edge(hh))
+}
+fn hh_face(mesh: &Mesh, hh: HalfedgeHandle) -> Option<FaceHandle> {
+    mesh.face(hh).to_option()
+}
+fn hh_opposite_face(mesh: &Mesh, hh: HalfedgeHandle) -> Option<FaceHandle> {
+    mesh.face(mesh.opposite_halfedge(hh)).to_option()
+}
+
+macro_rules! circulators {
+    ($Iter:ident, $Handle:ty, $anchor:ident, $map:ident,
+     $ccw_fn:ident, $ccw_step:ident, $cw_fn:ident, $cw_step:ident, $doc:expr) => {
+        #[doc = "Counter-clockwise circulator through the "]
+        #[doc = $doc]
+        pub fn $ccw_fn(&self, h: $Handle) -> $Iter<'_> {
+            Circulator::new(self, self.$anchor(h), Mesh::$ccw_step, $map)
+        }
+
+        #[doc = "Clockwise circulator through the "]
+        #[doc = $doc]
+        pub fn $cw_fn(&self, h: $Handle) -> $Iter<'_> {
+            Circulator::new(self, self.$anchor(h), Mesh::$cw_step, $map)
+        }
+    };
+}
+
+#[rustfmt::skip::macros(circulators)]
+impl Mesh {
+    circulators!(VertexVertexIter,   VertexHandle, vertex_halfedge, hh_to_vertex,
+                 vv_ccw_iter,  ccw_rotated,   vv_cw_iter,  cw_rotated,
+                 "vertices adjacent to a vertex.");
+    circulators!(VertexHalfedgeIter, VertexHandle, vertex_halfedge, hh_outgoing,
+                 voh_ccw_iter, ccw_rotated,   voh_cw_iter, cw_rotated,
+                 "outgoing halfedges of a vertex.");
+    circulators!(VertexHalfedgeIter, VertexHandle, vertex_halfedge, hh_incoming,
+                 vih_ccw_iter, ccw_rotated,   vih_cw_iter, cw_rotated,
+                 "incoming halfedges of a vertex.");
+    circulators!(VertexEdgeIter,     VertexHandle, vertex_halfedge, hh_edge,
+                 ve_ccw_iter,  ccw_rotated,   ve_cw_iter,  cw_rotated,
+                 "edges incident to a vertex.");
+    circulators!(VertexFaceIter,     VertexHandle, vertex_halfedge, hh_face,
+                 vf_ccw_iter,  ccw_rotated,   vf_cw_iter,  cw_rotated,
+                 "faces incident to a vertex, skipping boundary gaps.");
+    circulators!(FaceVertexIter,     FaceHandle,   face_halfedge,   hh_to_vertex,
+                 fv_ccw_iter,  next_halfedge, fv_cw_iter,  prev_halfedge,
+                 "vertices of a face.");
+    circulators!(FaceHalfedgeIter,   FaceHandle,   face_halfedge,   hh_outgoing,
+                 fh_ccw_iter,  next_halfedge, fh_cw_iter,  prev_halfedge,
+                 "halfedges of a face.");
+    circulators!(FaceEdgeIter,       FaceHandle,   face_halfedge,   hh_edge,
+                 fe_ccw_iter,  next_halfedge, fe_cw_iter,  prev_halfedge,
+                 "edges of a face.");
+    circulators!(FaceFaceIter,       FaceHandle,   face_halfedge,   hh_opposite_face,
+                 ff_ccw_iter,  next_halfedge, ff_cw_iter,  prev_halfedge,
+                 "faces adjacent to a face, skipping boundary edges.");
+}
+
 #[cfg(test)]
 mod test {
     use super::{BwdIter, FwdIter};
@@ -227,7 +496,7 @@ mod test {
     }
 
     fn is_even(i: &Index) -> bool {
-        i % 2 == 0
+        i.is_multiple_of(2)
     }
     fn is_odd(i: &Index) -> bool {
         i % 2 == 1
@@ -284,4 +553,179 @@ mod test {
             &(0..15).rev().filter(is_even).collect::<Vec<_>>()
         );
     }
+
+    ////////////////////////////////////////////////////////////
+    // Range iterators
+
+    fn indices<I: Iterator<Item = VertexHandle>>(iter: I) -> Vec<Index> {
+        iter.map(|vh| vh.index()).collect()
+    }
+
+    #[test]
+    fn test_item_iter() {
+        let mesh = with_status(Mesh::debug_triangles(5), is_even);
+        let odd = (0..15).filter(is_odd).collect::<Vec<_>>();
+        assert_eq!(indices(mesh.vertices().iter()), odd);
+        assert_eq!(
+            indices(mesh.vertices().rev()),
+            odd.iter().rev().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            indices(mesh.vertices().iter_all()),
+            (0..15).collect::<Vec<_>>()
+        );
+
+        // Without status, nothing is skipped.
+        let mesh = Mesh::debug_triangles(2);
+        assert_eq!(indices(mesh.vertices().iter()), (0..6).collect::<Vec<_>>());
+        let mut count = 0;
+        for _ in mesh.vertices().iter() {
+            count += 1;
+        }
+        assert_eq!(count, 6);
+
+        // Items beyond the end of a short status list are not skipped.
+        let mut mesh = with_status(Mesh::debug_triangles(2), is_even);
+        mesh.get_vertex_status_mut().unwrap().storage.truncate(3);
+        let iter = mesh.vertices().iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(indices(iter), vec![1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_item_iter_double_ended() {
+        let mesh = with_status(Mesh::debug_triangles(5), is_odd);
+        let mut iter = mesh.vertices().iter();
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.size_hint(), (8, Some(8)));
+        assert_eq!(iter.next().map(|h| h.index()), Some(0));
+        assert_eq!(iter.next_back().map(|h| h.index()), Some(14));
+        assert_eq!(iter.next_back().map(|h| h.index()), Some(12));
+        assert_eq!(iter.len(), 5);
+        assert_eq!(indices(iter), vec![2, 4, 6, 8, 10]);
+
+        let mut iter = mesh.vertices().iter_all();
+        assert_eq!(iter.len(), 15);
+        iter.next_back();
+        assert_eq!(iter.len(), 14);
+        assert_eq!(iter.by_ref().count(), 14);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Circulators
+
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::EdgeHandle;
+    use crate::mesh::test_util::{fan, open_fan};
+
+    /// Rotates the list so that it starts at its minimum element.
+    fn normalized<H: Handle>(list: Vec<H>) -> Vec<Index> {
+        let mut list = list.into_iter().map(|h| h.index()).collect::<Vec<_>>();
+        if let Some(pos) = (0..list.len()).min_by_key(|&i| list[i]) {
+            list.rotate_left(pos);
+        }
+        list
+    }
+
+    #[test]
+    fn test_vertex_circulators_closed() {
+        let (mesh, v, _) = fan(4);
+        assert_eq!(
+            normalized(mesh.vv_ccw_iter(v[0]).collect()),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            normalized(mesh.vv_cw_iter(v[0]).collect()),
+            vec![1, 4, 3, 2]
+        );
+        assert_eq!(
+            normalized(mesh.vf_ccw_iter(v[0]).collect()),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(
+            normalized(mesh.vf_cw_iter(v[0]).collect()),
+            vec![0, 3, 2, 1]
+        );
+        assert_eq!(mesh.ve_ccw_iter(v[0]).count(), 4);
+        for hh in mesh.voh_ccw_iter(v[0]) {
+            assert_eq!(mesh.from_vertex(hh), v[0]);
+        }
+        for hh in mesh.vih_cw_iter(v[0]) {
+            assert_eq!(mesh.to_vertex(hh), v[0]);
+        }
+        assert_eq!(
+            mesh.voh_ccw_iter(v[0])
+                .map(|hh| mesh.to_vertex(hh))
+                .collect::<Vec<_>>(),
+            mesh.vv_ccw_iter(v[0]).collect::<Vec<_>>()
+        );
+        // Ring vertex: center, and its two neighbors on the ring.
+        assert_eq!(normalized(mesh.vv_ccw_iter(v[1]).collect()), vec![0, 4, 2]);
+        assert_eq!(mesh.vf_ccw_iter(v[1]).count(), 2);
+    }
+
+    #[test]
+    fn test_vertex_circulators_open() {
+        let (mesh, v, f) = open_fan(4);
+        // Starts at the outgoing boundary halfedge.
+        assert_eq!(
+            mesh.vv_ccw_iter(v[0]).collect::<Vec<_>>(),
+            vec![v[4], v[1], v[2], v[3]]
+        );
+        assert_eq!(
+            mesh.vv_cw_iter(v[0]).collect::<Vec<_>>(),
+            vec![v[4], v[3], v[2], v[1]]
+        );
+        // Skips the boundary.
+        assert_eq!(
+            mesh.vf_ccw_iter(v[0]).collect::<Vec<_>>(),
+            vec![f[0], f[1], f[2]]
+        );
+        assert_eq!(
+            mesh.vf_cw_iter(v[0]).collect::<Vec<_>>(),
+            vec![f[2], f[1], f[0]]
+        );
+        assert_eq!(mesh.vf_ccw_iter(v[4]).collect::<Vec<_>>(), vec![f[2]]);
+    }
+
+    #[test]
+    fn test_face_circulators() {
+        let (mesh, v, f) = open_fan(4);
+        assert_eq!(
+            mesh.fv_ccw_iter(f[1]).collect::<Vec<_>>(),
+            vec![v[0], v[2], v[3]]
+        );
+        assert_eq!(
+            mesh.fv_cw_iter(f[1]).collect::<Vec<_>>(),
+            vec![v[0], v[3], v[2]]
+        );
+        for hh in mesh.fh_ccw_iter(f[1]) {
+            assert_eq!(mesh.face(hh), f[1]);
+        }
+        assert_eq!(
+            mesh.fh_cw_iter(f[1])
+                .map(|hh| mesh.edge(hh))
+                .collect::<Vec<EdgeHandle>>(),
+            mesh.fe_cw_iter(f[1]).collect::<Vec<_>>()
+        );
+        assert_eq!(normalized(mesh.ff_ccw_iter(f[1]).collect()), vec![0, 2]);
+        assert_eq!(mesh.ff_cw_iter(f[0]).collect::<Vec<_>>(), vec![f[1]]);
+    }
+
+    #[test]
+    fn test_circulator_copy_and_isolated() {
+        let (mut mesh, v, _) = fan(4);
+        let mut iter = mesh.vv_ccw_iter(v[0]);
+        iter.next();
+        let copy = iter;
+        assert_eq!(iter.count(), 3);
+        assert_eq!(copy.count(), 3);
+
+        let vh = mesh.add_vertex(Vec3::new(0.0, 0.0, 0.0));
+        assert_eq!(mesh.vv_ccw_iter(vh).count(), 0);
+        assert_eq!(mesh.vf_cw_iter(vh).count(), 0);
+    }
 }
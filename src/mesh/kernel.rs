@@ -0,0 +1,365 @@
+//! Connectivity navigation and low-level mutators, in the spirit of OpenMesh's `ArrayKernel`.
+//!
+//! The public navigation methods panic on out-of-bounds handles. The crate-private mutators
+//! neither check nor maintain any topological invariants. They are the building blocks for the
+//! higher-level mesh edit operations.
+use crate::mesh::item_handle::{
+    BoundaryItemHandle, EdgeHandle, FaceHandle, HalfedgeAnchorHandle, HalfedgeHandle, VertexHandle,
+};
+use crate::mesh::items::{Halfedge, Vertex};
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+impl Mesh {
+    ////////////////////////////////////////////////////////////
+    // Raw item access
+
+    /// The vertex item at `vh`. Panics if out of bounds.
+    pub(crate) fn vertex_item(&self, vh: VertexHandle) -> &Vertex {
+        &self.vertices[vh.index_us()]
+    }
+
+    /// The vertex item at `vh` mutably. Panics if out of bounds.
+    pub(crate) fn vertex_item_mut(&mut self, vh: VertexHandle) -> &mut Vertex {
+        &mut self.vertices[vh.index_us()]
+    }
+
+    /// The halfedge item at `hh`. Panics if out of bounds.
+    pub(crate) fn halfedge_item(&self, hh: HalfedgeHandle) -> &Halfedge {
+        let index = hh.index_us();
+        &self.edges[index / 2].0[index % 2]
+    }
+
+    /// The halfedge item at `hh` mutably. Panics if out of bounds.
+    pub(crate) fn halfedge_item_mut(&mut self, hh: HalfedgeHandle) -> &mut Halfedge {
+        let index = hh.index_us();
+        &mut self.edges[index / 2].0[index % 2]
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Halfedge connectivity
+
+    /// The halfedge following `hh` in its face or boundary loop.
+    pub fn next_halfedge(&self, hh: HalfedgeHandle) -> HalfedgeHandle {
+        self.halfedge_item(hh).hnext
+    }
+
+    /// The halfedge preceding `hh` in its face or boundary loop.
+    pub fn prev_halfedge(&self, hh: HalfedgeHandle) -> HalfedgeHandle {
+        self.halfedge_item(hh).hprev
+    }
+
+    /// The other halfedge of the edge containing `hh`. This does not access the mesh, so it does
+    /// not panic on out-of-bounds handles.
+    pub fn opposite_halfedge(&self, hh: HalfedgeHandle) -> HalfedgeHandle {
+        HalfedgeHandle::from_index(hh.index() ^ 1)
+    }
+
+    /// The vertex `hh` points to.
+    pub fn to_vertex(&self, hh: HalfedgeHandle) -> VertexHandle {
+        self.halfedge_item(hh).vh
+    }
+
+    /// The vertex `hh` starts from.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_vertex(&self, hh: HalfedgeHandle) -> VertexHandle {
+        self.to_vertex(self.opposite_halfedge(hh))
+    }
+
+    /// The face `hh` belongs to, or an invalid handle for boundary halfedges.
+    pub fn face(&self, hh: HalfedgeHandle) -> FaceHandle {
+        self.halfedge_item(hh).fh
+    }
+
+    /// The edge containing `hh`. This does not access the mesh, so it does not panic on
+    /// out-of-bounds handles.
+    pub fn edge(&self, hh: HalfedgeHandle) -> EdgeHandle {
+        EdgeHandle::from_index(hh.index() / 2)
+    }
+
+    /// The `i`th (0 or 1) halfedge of edge `eh`. This does not access the mesh, so it does not
+    /// panic on out-of-bounds handles.
+    pub fn edge_halfedge(&self, eh: EdgeHandle, i: u32) -> HalfedgeHandle {
+        assert!(i <= 1, "Edge halfedge index {} must be 0 or 1.", i);
+        HalfedgeHandle::from_index(eh.index() * 2 + i)
+    }
+
+    /// The next outgoing halfedge counter-clockwise around the vertex `hh` starts from.
+    pub fn ccw_rotated(&self, hh: HalfedgeHandle) -> HalfedgeHandle {
+        self.opposite_halfedge(self.prev_halfedge(hh))
+    }
+
+    /// The next outgoing halfedge clockwise around the vertex `hh` starts from.
+    pub fn cw_rotated(&self, hh: HalfedgeHandle) -> HalfedgeHandle {
+        self.next_halfedge(self.opposite_halfedge(hh))
+    }
+
+    /// For a vertex handle, an outgoing halfedge, or an invalid handle for isolated vertices.
+    /// For boundary vertices, this is a boundary halfedge.
+    ///
+    /// For a face handle, a halfedge bounding the face.
+    pub fn halfedge<H: HalfedgeAnchorHandle>(&self, h: H) -> HalfedgeHandle {
+        H::anchor_halfedge(self, h)
+    }
+
+    /// Whether the item is on the boundary:
+    ///
+    /// - a vertex if it is isolated or any of its incident halfedges has no face,
+    /// - a halfedge if it has no face,
+    /// - an edge if either of its halfedges has no face, and
+    /// - a face if any of its edges is on the boundary.
+    pub fn is_boundary<H: BoundaryItemHandle>(&self, h: H) -> bool {
+        H::is_boundary(self, h)
+    }
+
+    /// Sets the vertex `hh` points to.
+    pub(crate) fn set_to_vertex(&mut self, hh: HalfedgeHandle, vh: VertexHandle) {
+        self.halfedge_item_mut(hh).vh = vh;
+    }
+
+    /// Sets the face `hh` belongs to.
+    pub(crate) fn set_face(&mut self, hh: HalfedgeHandle, fh: FaceHandle) {
+        self.halfedge_item_mut(hh).fh = fh;
+    }
+
+    /// Links `hh` to `hnext`, also setting `hh` as the previous halfedge of `hnext`.
+    pub(crate) fn set_next_halfedge(&mut self, hh: HalfedgeHandle, hnext: HalfedgeHandle) {
+        self.halfedge_item_mut(hh).hnext = hnext;
+        self.halfedge_item_mut(hnext).hprev = hh;
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Vertex/face anchors
+
+    /// An outgoing halfedge of `vh`, or an invalid handle for isolated vertices.
+    /// For boundary vertices, this is a boundary halfedge if the connectivity is consistent.
+    pub(crate) fn vertex_halfedge(&self, vh: VertexHandle) -> HalfedgeHandle {
+        self.vertex_item(vh).hh
+    }
+
+    /// Sets the outgoing halfedge of `vh`.
+    pub(crate) fn set_vertex_halfedge(&mut self, vh: VertexHandle, hh: HalfedgeHandle) {
+        self.vertex_item_mut(vh).hh = hh;
+    }
+
+    /// A halfedge bounding `fh`.
+    pub(crate) fn face_halfedge(&self, fh: FaceHandle) -> HalfedgeHandle {
+        self.faces[fh.index_us()].hh
+    }
+
+    /// Sets the halfedge bounding `fh`.
+    pub(crate) fn set_face_halfedge(&mut self, fh: FaceHandle, hh: HalfedgeHandle) {
+        self.faces[fh.index_us()].hh = hh;
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Queries
+
+    /// Whether `hh` has no face.
+    pub(crate) fn is_boundary_halfedge(&self, hh: HalfedgeHandle) -> bool {
+        !self.face(hh).is_valid()
+    }
+
+    /// Whether `vh` is isolated or its outgoing halfedge lies on the boundary.
+    pub(crate) fn is_boundary_vertex(&self, vh: VertexHandle) -> bool {
+        let hh = self.vertex_halfedge(vh);
+        !(hh.is_valid() && self.face(hh).is_valid())
+    }
+
+    /// Whether either halfedge of `eh` lies on the boundary.
+    pub(crate) fn is_boundary_edge(&self, eh: EdgeHandle) -> bool {
+        self.is_boundary_halfedge(self.edge_halfedge(eh, 0))
+            || self.is_boundary_halfedge(self.edge_halfedge(eh, 1))
+    }
+
+    /// Whether any edge of `fh` lies on the boundary.
+    pub(crate) fn is_boundary_face(&self, fh: FaceHandle) -> bool {
+        let start = self.face_halfedge(fh);
+        let mut hh = start;
+        loop {
+            if self.is_boundary_halfedge(self.opposite_halfedge(hh)) {
+                return true;
+            }
+            hh = self.next_halfedge(hh);
+            if hh == start {
+                return false;
+            }
+        }
+    }
+
+    /// Number of edges incident to `vh`.
+    pub fn vertex_valence(&self, vh: VertexHandle) -> usize {
+        self.voh_ccw_iter(vh).count()
+    }
+
+    /// Number of vertices (equivalently, edges) of `fh`.
+    pub fn face_valence(&self, fh: FaceHandle) -> usize {
+        self.fh_ccw_iter(fh).count()
+    }
+
+    /// Finds the halfedge from `v0` to `v1`, or returns an invalid handle if there is none.
+    pub(crate) fn find_halfedge(&self, v0: VertexHandle, v1: VertexHandle) -> HalfedgeHandle {
+        let start = self.vertex_halfedge(v0);
+        if start.is_valid() {
+            let mut hh = start;
+            loop {
+                if self.to_vertex(hh) == v1 {
+                    return hh;
+                }
+                hh = self.cw_rotated(hh);
+                if hh == start {
+                    break;
+                }
+            }
+        }
+        HalfedgeHandle::new()
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Construction
+
+    /// Adds an edge from `v0` to `v1` and returns the halfedge from `v0` to `v1`. The opposite
+    /// halfedge is the one following it in index order. Halfedges are otherwise unlinked.
+    pub(crate) fn new_edge(&mut self, v0: VertexHandle, v1: VertexHandle) -> HalfedgeHandle {
+        let eh = self.edges_mut().append();
+        self.h_props.push_all();
+        self.h_props.push_all();
+        let hh0 = self.edge_halfedge(eh, 0);
+        let hh1 = self.edge_halfedge(eh, 1);
+        self.set_to_vertex(hh0, v1);
+        self.set_to_vertex(hh1, v0);
+        hh0
+    }
+
+    /// Adds an unlinked face.
+    pub(crate) fn new_face(&mut self) -> FaceHandle {
+        self.faces_mut().append()
+    }
+
+    /// Makes the outgoing halfedge of `vh` a boundary halfedge, if there is any. This is required
+    /// for boundary vertices for fast boundary checks and for circulation to start at the
+    /// boundary.
+    pub(crate) fn adjust_outgoing_halfedge(&mut self, vh: VertexHandle) {
+        let start = self.vertex_halfedge(vh);
+        if !start.is_valid() {
+            return;
+        }
+        let mut hh = start;
+        loop {
+            if self.is_boundary_halfedge(hh) {
+                self.set_vertex_halfedge(vh, hh);
+                return;
+            }
+            hh = self.cw_rotated(hh);
+            if hh == start {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    fn triangle() -> Mesh {
+        let zero = Vec3::new(0.0, 0.0, 0.0);
+        Mesh::triangle(zero, zero, zero)
+    }
+
+    #[test]
+    fn halfedge_navigation() {
+        let mesh = triangle();
+        let hh = HalfedgeHandle::from_index;
+        let vh = VertexHandle::from_index;
+        assert_eq!(mesh.next_halfedge(hh(0)), hh(2));
+        assert_eq!(mesh.prev_halfedge(hh(0)), hh(4));
+        assert_eq!(mesh.opposite_halfedge(hh(0)), hh(1));
+        assert_eq!(mesh.opposite_halfedge(hh(5)), hh(4));
+        assert_eq!(mesh.to_vertex(hh(0)), vh(0));
+        assert_eq!(mesh.from_vertex(hh(0)), vh(2));
+        assert_eq!(mesh.face(hh(0)), FaceHandle::from_index(0));
+        assert!(!mesh.face(hh(1)).is_valid());
+        assert_eq!(mesh.edge(hh(5)), EdgeHandle::from_index(2));
+        assert_eq!(mesh.edge_halfedge(EdgeHandle::from_index(2), 0), hh(4));
+        assert_eq!(mesh.edge_halfedge(EdgeHandle::from_index(2), 1), hh(5));
+    }
+
+    #[test]
+    fn halfedge_loops() {
+        let mesh = Mesh::debug_triangles(3);
+        for i in 0..mesh.halfedges().len() {
+            let hh = HalfedgeHandle::from_index(i);
+            let next = mesh.next_halfedge(hh);
+            assert_eq!(mesh.prev_halfedge(next), hh);
+            assert_eq!(mesh.from_vertex(next), mesh.to_vertex(hh));
+            assert_eq!(mesh.face(next), mesh.face(hh));
+            assert_eq!(mesh.next_halfedge(mesh.next_halfedge(next)), hh);
+        }
+    }
+
+    #[test]
+    fn anchors() {
+        let mesh = Mesh::debug_triangles(2);
+        for i in 0..mesh.vertices().len() {
+            let vh = VertexHandle::from_index(i);
+            let hh = mesh.halfedge(vh);
+            assert_eq!(mesh.from_vertex(hh), vh);
+            assert!(mesh.is_boundary(hh));
+        }
+        for i in 0..mesh.faces().len() {
+            let fh = FaceHandle::from_index(i);
+            assert_eq!(mesh.face(mesh.halfedge(fh)), fh);
+        }
+    }
+
+    #[test]
+    fn rotation() {
+        let mesh = triangle();
+        let vh = VertexHandle::from_index(0);
+        let hh = mesh.halfedge(vh);
+        // Two outgoing halfedges: the boundary one and the face one.
+        let cw = mesh.cw_rotated(hh);
+        assert_ne!(cw, hh);
+        assert_eq!(mesh.from_vertex(cw), vh);
+        assert_eq!(mesh.cw_rotated(cw), hh);
+        assert_eq!(mesh.ccw_rotated(hh), cw);
+        assert_eq!(mesh.ccw_rotated(cw), hh);
+    }
+
+    #[test]
+    fn boundary() {
+        let mut mesh = triangle();
+        assert!(mesh.is_boundary(VertexHandle::from_index(0)));
+        assert!(mesh.is_boundary(EdgeHandle::from_index(0)));
+        assert!(mesh.is_boundary(FaceHandle::from_index(0)));
+        assert!(!mesh.is_boundary(HalfedgeHandle::from_index(0)));
+        assert!(mesh.is_boundary(HalfedgeHandle::from_index(1)));
+
+        // Close the triangle into a tetrahedron.
+        let v = (0..3).map(VertexHandle::from_index).collect::<Vec<_>>();
+        let apex = mesh.add_vertex(Vec3::new(0.0, 0.0, 1.0));
+        mesh.add_face(&[v[1], v[0], apex]).unwrap();
+        mesh.add_face(&[v[2], v[1], apex]).unwrap();
+        assert!(mesh.is_boundary(FaceHandle::from_index(1)));
+        mesh.add_face(&[v[0], v[2], apex]).unwrap();
+        for i in 0..4 {
+            assert!(!mesh.is_boundary(VertexHandle::from_index(i)));
+            assert!(!mesh.is_boundary(FaceHandle::from_index(i)));
+        }
+        for i in 0..6 {
+            assert!(!mesh.is_boundary(EdgeHandle::from_index(i)));
+        }
+    }
+
+    #[test]
+    fn isolated_vertex() {
+        let mut mesh = Mesh::new();
+        let vh = mesh.add_vertex(Vec3::new(0.0, 0.0, 0.0));
+        assert!(!mesh.halfedge(vh).is_valid());
+        assert!(mesh.is_boundary(vh));
+    }
+}
@@ -0,0 +1,174 @@
+//! Lenient face construction for importing meshes that are not 2-manifold, in the spirit of
+//! OpenMesh's `ImporterT::add_face()`.
+use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+
+/// Result of `Mesh::add_face_lenient()`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LenientFace {
+    /// The new face.
+    pub fh: FaceHandle,
+    /// Pairs of an original vertex and the duplicate that replaced it in the new face, in the
+    /// order they were created. Empty if the face was added as is.
+    pub duplicated: Vec<(VertexHandle, VertexHandle)>,
+}
+
+impl LenientFace {
+    /// Whether any vertex had to be duplicated to add the face.
+    pub fn is_fixed(&self) -> bool {
+        !self.duplicated.is_empty()
+    }
+}
+
+impl Mesh {
+    /// Adds a face like `Mesh::add_face()`, but duplicates vertices where the face would make
+    /// the mesh non-manifold instead of failing. Intended for importing scan data with bow-tie
+    /// vertices and edges shared by more than two faces.
+    ///
+    /// - A complex vertex, or one around which the existing faces leave no gap for the new face,
+    ///   is replaced in the new face by a duplicate.
+    /// - A complex edge, or one whose existing face has the orientation of the new face, is split
+    ///   off by duplicating one of its endpoints, so that the new face gets its own edge.
+    ///
+    /// Duplicates copy all the vertex properties of the original and are marked
+    /// `FIXED_NON_MANIFOLD`. The vertex status must be available.
+    ///
+    /// Fails like `Mesh::add_face()` if there are fewer than 3 distinct vertices or any vertex
+    /// handle is invalid, in which case the mesh is left unchanged.
+    pub fn add_face_lenient(&mut self, vhs: &[VertexHandle]) -> Result<LenientFace> {
+        if self.get_vertex_status().is_none() {
+            return Err(TopologyError::MissingStatus("vertex"));
+        }
+        let mut vhs = vhs.to_vec();
+        let mut duplicated: Vec<(VertexHandle, VertexHandle)> = Vec::new();
+        loop {
+            let vh = match self.add_face(&vhs) {
+                Ok(fh) => return Ok(LenientFace { fh, duplicated }),
+                Err(TopologyError::ComplexVertex(vh))
+                | Err(TopologyError::PatchRelinkingFailed(vh)) => vh,
+                Err(TopologyError::ComplexEdge(v0, v1))
+                | Err(TopologyError::FlippedOrientation(v0, v1)) => {
+                    // Edges from duplicates are always new, so at most one endpoint is one.
+                    if duplicated.iter().any(|&(_, dup)| dup == v0) {
+                        v1
+                    } else {
+                        v0
+                    }
+                }
+                Err(err) => return Err(err),
+            };
+            // Duplicates are isolated when added and so never fail, which bounds the loop by the
+            // number of vertices in the face.
+            let dup = self.duplicate_vertex(vh);
+            for v in vhs.iter_mut().filter(|v| **v == vh) {
+                *v = dup;
+            }
+            duplicated.push((vh, dup));
+        }
+    }
+
+    /// Adds an isolated copy of `vh` with all its properties, marked `FIXED_NON_MANIFOLD`.
+    fn duplicate_vertex(&mut self, vh: VertexHandle) -> VertexHandle {
+        let dup = self.add_vertex(self.point(vh));
+        self.v_props.copy_all(vh, dup);
+        let status = self
+            .get_vertex_status_mut()
+            .expect("Vertex status checked by the caller");
+        status[dup].set_fixed_non_manifold(true);
+        dup
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mesh::item_handle::VertexHandle;
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, fan, mesh_with_vertices};
+    use crate::property::Handle;
+
+    #[test]
+    fn manifold_face() {
+        let (mut mesh, v) = mesh_with_vertices(3);
+        mesh.request_vertex_status();
+        let res = mesh.add_face_lenient(&v).unwrap();
+        assert!(!res.is_fixed());
+        assert_eq!(mesh.vertices().len(), 3);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn bow_tie_vertex() {
+        // The fan is closed around vertex 0, so a further face at vertex 0 makes a bow tie.
+        let (mut mesh, v, _) = fan(4);
+        mesh.request_vertex_status();
+        let a = mesh.add_vertex(Default::default());
+        let b = mesh.add_vertex(Default::default());
+        assert_eq!(
+            mesh.add_face(&[v[0], a, b]),
+            Err(TopologyError::ComplexVertex(v[0]))
+        );
+        let res = mesh.add_face_lenient(&[v[0], a, b]).unwrap();
+        assert_eq!(res.duplicated.len(), 1);
+        let (orig, dup) = res.duplicated[0];
+        assert_eq!(orig, v[0]);
+        assert_eq!(mesh.point(dup), mesh.point(v[0]));
+        let status = mesh.get_vertex_status().unwrap();
+        assert!(status[dup].fixed_non_manifold());
+        assert!(!status[v[0]].fixed_non_manifold());
+        assert_eq!(mesh.fv_ccw_iter(res.fh).filter(|&vh| vh == dup).count(), 1);
+        assert_eq!(mesh.vertex_valence(v[0]), 4);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn complex_edge() {
+        // Three faces sharing the edge (0, 1).
+        let (mut mesh, v) = mesh_with_vertices(5);
+        mesh.request_vertex_status();
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        mesh.add_face(&[v[1], v[0], v[3]]).unwrap();
+        let res = mesh.add_face_lenient(&[v[0], v[1], v[4]]).unwrap();
+        // Vertex 0 is duplicated as the new vertex 5, so the new face gets the edge (5, 1).
+        let dup = VertexHandle::from_index(5);
+        assert_eq!(res.duplicated, vec![(v[0], dup)]);
+        assert!(mesh.get_vertex_status().unwrap()[dup].fixed_non_manifold());
+        assert_eq!(
+            mesh.fv_ccw_iter(res.fh).collect::<Vec<_>>(),
+            vec![dup, v[1], v[4]]
+        );
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[1]));
+        for fh in mesh.faces().iter() {
+            let shares_edge = mesh.fh_ccw_iter(fh).any(|hh| mesh.edge(hh) == eh);
+            assert_eq!(shares_edge, fh != res.fh);
+        }
+        assert_eq!(mesh.faces().iter().count(), 3);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn flipped_orientation() {
+        let (mut mesh, v) = mesh_with_vertices(4);
+        mesh.request_vertex_status();
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        let res = mesh.add_face_lenient(&[v[0], v[1], v[3]]).unwrap();
+        assert_eq!(res.duplicated.len(), 1);
+        assert_eq!(mesh.edges().iter().count(), 6);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn lenient_fails() {
+        let (mut mesh, v) = mesh_with_vertices(3);
+        assert_eq!(
+            mesh.add_face_lenient(&v),
+            Err(TopologyError::MissingStatus("vertex"))
+        );
+        mesh.request_vertex_status();
+        assert_eq!(
+            mesh.add_face_lenient(&[v[0], v[1], v[0]]),
+            Err(TopologyError::DegenerateFace)
+        );
+        assert_eq!(mesh.vertices().len(), 3);
+    }
+}
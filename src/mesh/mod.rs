@@ -1,5 +1,6 @@
 //! 2-manifold surface mesh represented as a halfedge data structure.
 
+use crate::geometry::vector::Vec3;
 use crate::mesh::item_handle::{
     EdgeHandle, FaceHandle, HalfedgeHandle, MeshHandle, MeshItemHandle, VertexHandle,
 };
@@ -13,23 +14,48 @@ use crate::mesh::prop::{
 };
 use crate::mesh::rc::{RcEPropHandle, RcFPropHandle, RcHPropHandle, RcVPropHandle};
 use crate::mesh::status::Status;
-use crate::property::PropertyContainer;
 use crate::property::Size;
+use crate::property::{PropertyContainer, VPropHandle};
 
 // TODO: Determine carefully what ought to be reexported.
 pub mod item_handle;
 pub mod iter;
 pub mod status;
 
+pub mod append;
+pub mod check;
+pub mod handle_map;
+pub mod hole;
 pub mod items;
+pub mod lenient;
 pub mod prop;
+pub mod reorder;
+pub mod result;
+pub mod tri_mesh;
+pub mod vertex_cache;
 
 // Mesh's distributed impl's.
+mod attrib;
+mod boundary;
+mod collapse;
+mod components;
+mod connectivity;
 mod constructor;
+mod delete;
+mod extract;
+mod flip;
+mod garbage;
+mod kernel;
+mod orientation;
+mod permute;
 mod rc;
+mod split;
+#[cfg(test)]
+mod test_util;
+mod triangulate;
 
 /// Halfedge data structure.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Mesh {
     // Item connectivity and properties.
     pub(crate) vertices: Vec<Vertex>,
@@ -49,6 +75,32 @@ pub struct Mesh {
     pub(crate) h_status: RcHPropHandle<Status>,
     pub(crate) e_status: RcEPropHandle<Status>,
     pub(crate) f_status: RcFPropHandle<Status>,
+
+    // See `impl` in `attrib.rs`.
+    // Handle for the always-present vertex positions.
+    pub(crate) points: VPropHandle<Vec3<f64>>,
+}
+
+impl Default for Mesh {
+    fn default() -> Self {
+        let mut v_props = PropertyContainer::default();
+        let points = attrib::add_points_prop(&mut v_props);
+        Mesh {
+            vertices: Default::default(),
+            edges: Default::default(),
+            faces: Default::default(),
+            v_props,
+            h_props: Default::default(),
+            e_props: Default::default(),
+            f_props: Default::default(),
+            m_props: Default::default(),
+            v_status: Default::default(),
+            h_status: Default::default(),
+            e_status: Default::default(),
+            f_status: Default::default(),
+            points,
+        }
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -64,7 +116,7 @@ impl Mesh {
     /// Returns the property container associated with the mesh item type identified by `Handle`.
     pub(crate) fn items<H: MeshItemHandle>(&self) -> Items<'_, H> {
         let (items, props) = <H as MeshItemHandle>::items_props(self);
-        Items::new(items, props)
+        Items::new(items, props, <H as MeshItemHandle>::status_prop(self))
     }
 
     /// Returns the property container associated with the mesh item type identified by `H`.
@@ -92,7 +144,8 @@ impl Mesh {
 
 // Public accessor methods
 macro_rules! item_accessors {
-    ($Handle:ty, $method:ident, $method_mut:ident, $Struct:ident, $StructMut:ident, $item:expr) => {
+    ($Handle:ty, $method:ident, $method_mut:ident, $Struct:ident, $StructMut:ident, $item:expr
+     $(, $note:expr)?) => {
         #[doc = "Returns a struct to access "]
         #[doc=$item]
         #[doc = " mesh items and properties."]
@@ -139,6 +192,7 @@ impl Mesh {
     pub fn reserve(&mut self, nv: Size, ne: Size, nf: Size) {
         self.vertices_mut().reserve(nv);
         self.edges_mut().reserve(ne);
+        self.h_props.reserve_all(2 * ne);
         self.faces_mut().reserve(nf);
     }
 
@@ -153,6 +207,7 @@ impl Mesh {
     pub(crate) fn resize(&mut self, nv: Size, ne: Size, nf: Size) {
         self.vertices_mut().resize(nv);
         self.edges_mut().resize(ne);
+        self.h_props.resize_all(2 * ne);
         self.faces_mut().resize(nf);
     }
 
@@ -162,6 +217,7 @@ impl Mesh {
     pub fn clear(&mut self) {
         self.vertices_mut().clear();
         self.edges_mut().clear();
+        self.h_props.clear_all();
         self.faces_mut().clear();
     }
 }
@@ -171,7 +227,8 @@ impl Mesh {
 
 // Public accessor methods
 macro_rules! prop_accessors {
-    ($Handle:ty, $method:ident, $method_mut:ident, $Struct:ident, $StructMut:ident, $item:expr) => {
+    ($Handle:ty, $method:ident, $method_mut:ident, $Struct:ident, $StructMut:ident, $item:expr
+     $(, $note:expr)?) => {
         #[doc = "Returns a struct to access "]
         #[doc=$item]
         #[doc = " properties."]
@@ -182,6 +239,7 @@ macro_rules! prop_accessors {
         #[doc = "Returns a struct to mutably access "]
         #[doc=$item]
         #[doc = " properties."]
+        $(#[doc = ""] #[doc = $note])?
         pub fn $method_mut(&mut self) -> $StructMut<'_> {
             self.props_mut()
         }
@@ -191,7 +249,9 @@ macro_rules! prop_accessors {
 #[rustfmt::skip::macros(prop_accessors)]
 impl Mesh {
     // Property accessors
-    prop_accessors!(  VertexHandle, v_props, v_props_mut, VProps, VPropsMut,   "vertex");
+    prop_accessors!(  VertexHandle, v_props, v_props_mut, VProps, VPropsMut,   "vertex",
+        "The vertex positions \"v:points\" must not be removed, since `Mesh::point()` and the \
+         other position accessors panic without them.");
     prop_accessors!(HalfedgeHandle, h_props, h_props_mut, HProps, HPropsMut, "halfedge");
     prop_accessors!(    EdgeHandle, e_props, e_props_mut, EProps, EPropsMut,     "edge");
     prop_accessors!(    FaceHandle, f_props, f_props_mut, FProps, FPropsMut,     "face");
@@ -235,20 +295,120 @@ impl<'a> ::std::fmt::Debug for FormattedPropStats<'a> {
 // Connectivity object constructors and accessors
 
 impl Mesh {
-    /// ArrayKernel uses the default copy constructor and assignment operator, which means
-    /// that the connectivity and all properties are copied, including reference
-    /// counters, allocated bit status masks, etc.. In contrast assign_connectivity
-    /// copies only the connectivity, i.e. vertices, edges, faces and their status fields.
-    /// NOTE: The geometry (the points property) is NOT copied. Poly/TriConnectivity
-    /// override(and hide) that function to provide connectivity consistence.
-    pub fn assign_connectivity<M>(&mut self, _mesh: &M) {
-        // TODO: Note that Poly/Tri connectivity cannot "override" this. So this has to be
-        // implemented with those taken into account.
-        unimplemented!()
+    /// `Clone` copies the connectivity and all properties, including the reference counts of
+    /// the status properties. In contrast, `assign_connectivity` copies only the connectivity,
+    /// i.e. vertices, edges, faces and their status fields.
+    ///
+    /// All other properties of this mesh, including the geometry (the points property), are kept
+    /// but resized to the new item counts. Status properties that exist on `other` are requested
+    /// on this mesh if needed; status properties only on this mesh are kept, but resized and
+    /// cleared, so that no flags of the old items carry over to the new ones.
+    pub fn assign_connectivity(&mut self, other: &Mesh) {
+        self.vertices.clone_from(&other.vertices);
+        self.edges.clone_from(&other.edges);
+        self.faces.clone_from(&other.faces);
+        let (nv, ne, nf) = (
+            other.vertices().len(),
+            other.edges().len(),
+            other.faces().len(),
+        );
+        self.v_props.resize_all(nv);
+        self.h_props.resize_all(2 * ne);
+        self.e_props.resize_all(ne);
+        self.f_props.resize_all(nf);
+
+        self.assign_status::<VertexHandle>(other, Mesh::request_vertex_status);
+        self.assign_status::<HalfedgeHandle>(other, Mesh::request_halfedge_status);
+        self.assign_status::<EdgeHandle>(other, Mesh::request_edge_status);
+        self.assign_status::<FaceHandle>(other, Mesh::request_face_status);
+    }
+
+    /// Copies the status property of item type `H` from `other` if it exists there, first
+    /// requesting it on this mesh with `request` if needed. Otherwise clears all flags of the
+    /// status property of this mesh, if any.
+    fn assign_status<H: MeshItemHandle>(&mut self, other: &Mesh, request: fn(&mut Mesh)) {
+        if let Some(src) = H::status_prop(other) {
+            if H::status_prop(self).is_none() {
+                request(self);
+            }
+            let dst = H::status_prop_mut(self).expect("Status property was just requested.");
+            dst.storage.clone_from(&src.storage);
+        } else if let Some(dst) = H::status_prop_mut(self) {
+            for status in dst.storage.iter_mut() {
+                *status = Status::empty();
+            }
+        }
     }
 
     // TODO
-    // - garbage_collection()
     // - resize(_, _, _)
     // - reserve(_, _, _)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    #[test]
+    fn assign_connectivity() {
+        let zero = Vec3::new(0.0, 0.0, 0.0);
+        let mut reference = Mesh::new();
+        let v = (0..4)
+            .map(|_| reference.add_vertex(zero))
+            .collect::<Vec<_>>();
+        reference.add_face(&[v[0], v[1], v[2]]).unwrap();
+        reference.add_face(&[v[0], v[2], v[3]]).unwrap();
+        reference.request_face_status();
+        reference.get_face_status_mut().unwrap()[FaceHandle::from_index(1)].set_selected(true);
+
+        let mut mesh = Mesh::triangle(
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        );
+        let weight = mesh.v_props_mut().add::<f32>(Some("weight".into()));
+        mesh.v_props_mut().get_mut(weight).unwrap()[VertexHandle::from_index(0)] = 2.0;
+
+        mesh.assign_connectivity(&reference);
+        assert_eq!(mesh.vertices().len(), 4);
+        assert_eq!(mesh.edges().len(), 5);
+        assert_eq!(mesh.halfedges().len(), 10);
+        assert_eq!(mesh.faces().len(), 2);
+        assert_eq!(
+            mesh.fv_ccw_iter(FaceHandle::from_index(1))
+                .collect::<Vec<_>>(),
+            vec![v[0], v[2], v[3]]
+        );
+
+        // Geometry and custom properties are kept, but resized.
+        assert_eq!(mesh.point(v[0]), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(mesh.point(v[3]), zero);
+        let weights = mesh.v_props().get(weight).unwrap();
+        assert_eq!(weights.storage, vec![2.0, 0.0, 0.0, 0.0]);
+        assert_eq!(mesh.h_props().len(), 10);
+
+        // Status is copied.
+        assert!(mesh.get_vertex_status().is_none());
+        let status = mesh.get_face_status().unwrap();
+        assert!(!status[FaceHandle::from_index(0)].selected());
+        assert!(status[FaceHandle::from_index(1)].selected());
+
+        // Status only on this mesh is cleared.
+        mesh.request_vertex_status();
+        mesh.get_vertex_status_mut().unwrap()[v[1]].set_deleted(true);
+        mesh.get_vertex_status_mut().unwrap()[v[2]].set_selected(true);
+        mesh.assign_connectivity(&reference);
+        let status = mesh.get_vertex_status().unwrap();
+        assert_eq!(status.storage.len(), 4);
+        assert!(status.storage.iter().all(|s| s.is_empty()));
+
+        // Resetting to a smaller topology.
+        mesh.assign_connectivity(&Mesh::new());
+        assert_eq!(mesh.vertices().len(), 0);
+        assert!(mesh.points().is_empty());
+        assert!(mesh.get_face_status().unwrap().storage.is_empty());
+    }
+}
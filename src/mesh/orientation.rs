@@ -0,0 +1,465 @@
+//! Face orientation, i.e. the direction of the halfedge loops around faces.
+use std::collections::{HashMap, VecDeque};
+
+use crate::geometry::vector::Vec3;
+use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+/// Distance within which the endpoints of the edges of a seam must be, relative to the diagonal
+/// of the bounding box of the boundary. See `Mesh::orient_consistently()`.
+const SEAM_TOLERANCE: f64 = 1e-9;
+
+impl Mesh {
+    /// Reverses the orientation of all faces, so that their vertices are in clockwise order
+    /// instead. Each halfedge is reversed in place by pointing it to its from-vertex and
+    /// swapping its next and previous halfedges.
+    ///
+    /// Halfedge properties describe the corner of a face (or boundary loop) at the halfedge's
+    /// to-vertex, so each halfedge takes over the properties of its previous halfedge, which
+    /// ended at the same vertex.
+    pub fn reverse_orientation(&mut self) {
+        self.reverse_faces(|_| true);
+    }
+
+    /// Makes the orientation of the faces agree across seams, where the faces of a mesh imported
+    /// with `Mesh::add_face_lenient()` were split apart because they disagreed. Faces sharing an
+    /// edge always agree, so this only changes meshes built with `Mesh::add_face_lenient()`,
+    /// and does nothing for any other mesh. Each patch of faces connected by edges is either
+    /// kept or reversed as a whole, as by `Mesh::reverse_orientation()`. The patches are visited
+    /// in a breadth-first search across seams, which keeps the orientation of the patch with the
+    /// first face of each search.
+    ///
+    /// A seam is a pair of boundary edges whose endpoints are within 1e-9 times the diagonal of
+    /// the bounding box of the boundary of each other, at least one of which is a vertex marked
+    /// `FIXED_NON_MANIFOLD`, i.e. a duplicate added by `Mesh::add_face_lenient()`. Boundaries of
+    /// separate sheets that merely touch are thus never seams.
+    ///
+    /// Returns the pairs of seam edges across which the faces still disagree, since no choice
+    /// of orientations satisfies all seams, e.g. for a Möbius strip. Fails without changing the
+    /// mesh if the vertex status is missing.
+    pub fn orient_consistently(&mut self) -> Result<Vec<(EdgeHandle, EdgeHandle)>> {
+        let seams = self.seams()?;
+        // Patch of each face that is not DELETED.
+        let mut patch = vec![None; self.faces.len()];
+        let mut n_patches = 0;
+        let mut queue = VecDeque::new();
+        for root in self.faces().iter_all() {
+            if patch[root.index_us()].is_some() || self.is_deleted(root) {
+                continue;
+            }
+            patch[root.index_us()] = Some(n_patches);
+            queue.push_back(root);
+            while let Some(fh) = queue.pop_front() {
+                for hh in self.fh_ccw_iter(fh) {
+                    let nb = self.face(self.opposite_halfedge(hh));
+                    if nb.is_valid() && patch[nb.index_us()].is_none() {
+                        patch[nb.index_us()] = Some(n_patches);
+                        queue.push_back(nb);
+                    }
+                }
+            }
+            n_patches += 1;
+        }
+        let seam_patches = |mesh: &Mesh, h0, h1| {
+            let (f0, f1) = mesh.seam_faces(h0, h1);
+            (
+                patch[f0.index_us()].expect("Seam faces are not DELETED"),
+                patch[f1.index_us()].expect("Seam faces are not DELETED"),
+            )
+        };
+
+        let mut links = vec![Vec::new(); n_patches];
+        for &(h0, h1, same_way) in &seams {
+            let (p0, p1) = seam_patches(self, h0, h1);
+            links[p0].push((p1, same_way));
+            links[p1].push((p0, same_way));
+        }
+        let mut flip = vec![None; n_patches];
+        for root in 0..n_patches {
+            if flip[root].is_some() {
+                continue;
+            }
+            flip[root] = Some(false);
+            let mut patches = VecDeque::new();
+            patches.push_back(root);
+            while let Some(p) = patches.pop_front() {
+                let flip_p = flip[p] == Some(true);
+                for &(nb, same_way) in &links[p] {
+                    if flip[nb].is_none() {
+                        flip[nb] = Some(flip_p ^ same_way);
+                        patches.push_back(nb);
+                    }
+                }
+            }
+        }
+        let flip: Vec<bool> = flip.into_iter().map(|f| f == Some(true)).collect();
+
+        let conflicts = seams
+            .iter()
+            .filter(|&&(h0, h1, same_way)| {
+                let (p0, p1) = seam_patches(self, h0, h1);
+                flip[p0] ^ flip[p1] != same_way
+            })
+            .map(|&(h0, h1, _)| (self.edge(h0), self.edge(h1)))
+            .collect();
+        self.reverse_faces(|fh| fh.is_valid() && patch[fh.index_us()].is_some_and(|p| flip[p]));
+        Ok(conflicts)
+    }
+
+    /// The seams (see `Mesh::orient_consistently()`) as pairs of boundary halfedges, and whether
+    /// they go the same way, in which case the faces across them disagree.
+    fn seams(&self) -> Result<Vec<(HalfedgeHandle, HalfedgeHandle, bool)>> {
+        let status = self
+            .get_vertex_status()
+            .ok_or(TopologyError::MissingStatus("vertex"))?;
+        let is_fixed = |hh| {
+            status[self.from_vertex(hh)].fixed_non_manifold()
+                || status[self.to_vertex(hh)].fixed_non_manifold()
+        };
+        // Edges without faces are skipped.
+        let boundary: Vec<HalfedgeHandle> = self
+            .boundary_halfedges()
+            .filter(|&hh| !self.is_boundary_halfedge(self.opposite_halfedge(hh)))
+            .collect();
+        if boundary.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (min, max) = boundary
+            .iter()
+            .map(|&hh| self.point(self.to_vertex(hh)))
+            .fold(
+                (Vec3::repeat(f64::INFINITY), Vec3::repeat(f64::NEG_INFINITY)),
+                |(min, max), p| (min.inf(&p), max.sup(&p)),
+            );
+        let tolerance = SEAM_TOLERANCE * (max - min).norm();
+        let near = |v0, v1| (self.point(v0) - self.point(v1)).norm() <= tolerance;
+
+        // Grid of cells of the size of the tolerance, holding the halfedges with an endpoint in
+        // each cell, so that the halfedges with an endpoint within the tolerance of a point are
+        // in the cells around it.
+        let scale = if tolerance > 0.0 {
+            1.0 / tolerance
+        } else {
+            0.0
+        };
+        let cell = |vh| {
+            let d = (self.point(vh) - min) * scale;
+            [d.x as i64, d.y as i64, d.z as i64]
+        };
+        let mut grid: HashMap<[i64; 3], Vec<HalfedgeHandle>> = HashMap::new();
+        for &hh in &boundary {
+            let (c0, c1) = (cell(self.from_vertex(hh)), cell(self.to_vertex(hh)));
+            grid.entry(c0).or_default().push(hh);
+            if c1 != c0 {
+                grid.entry(c1).or_default().push(hh);
+            }
+        }
+
+        let mut seams = Vec::new();
+        for &h0 in boundary.iter().filter(|&&hh| is_fixed(hh)) {
+            let [x, y, z] = cell(self.from_vertex(h0));
+            let mut candidates: Vec<HalfedgeHandle> = (0..27)
+                .filter_map(|i| grid.get(&[x + i % 3 - 1, y + i / 3 % 3 - 1, z + i / 9 - 1]))
+                .flatten()
+                .cloned()
+                .collect();
+            candidates.sort_by_key(|hh| hh.index());
+            candidates.dedup();
+            // Pairs of halfedges that are both fixed are found from the first one.
+            for h1 in candidates {
+                if h1 == h0 || (is_fixed(h1) && h1.index() < h0.index()) {
+                    continue;
+                }
+                let (a0, b0) = (self.from_vertex(h0), self.to_vertex(h0));
+                let (a1, b1) = (self.from_vertex(h1), self.to_vertex(h1));
+                if near(a0, a1) && near(b0, b1) {
+                    seams.push((h0, h1, true));
+                } else if near(a0, b1) && near(b0, a1) {
+                    seams.push((h0, h1, false));
+                }
+            }
+        }
+        Ok(seams)
+    }
+
+    /// The faces across the boundary halfedges of a seam.
+    fn seam_faces(&self, h0: HalfedgeHandle, h1: HalfedgeHandle) -> (FaceHandle, FaceHandle) {
+        (
+            self.face(self.opposite_halfedge(h0)),
+            self.face(self.opposite_halfedge(h1)),
+        )
+    }
+
+    /// Reverses the halfedges that are not DELETED and belong to an edge with a face for which
+    /// `flip` holds, or with no face if `flip` holds for the invalid handle, as in
+    /// `Mesh::reverse_orientation()`. `flip` must hold for all or none of the faces sharing an
+    /// edge.
+    ///
+    /// The fans around a vertex with both reversed and kept edges are relinked in the order of
+    /// their boundary halfedges, so the boundary loops through such a vertex may change.
+    fn reverse_faces(&mut self, flip: impl Fn(FaceHandle) -> bool) {
+        let is_flipped =
+            |mesh: &Mesh, hh| flip(mesh.face(hh)) || flip(mesh.face(mesh.opposite_halfedge(hh)));
+        let hhs: Vec<HalfedgeHandle> = self
+            .halfedges()
+            .iter_all()
+            .filter(|&hh| !self.is_deleted(self.edge(hh)) && is_flipped(self, hh))
+            .collect();
+        // Outgoing halfedges of the vertices with both reversed and kept edges.
+        let mixed: Vec<(VertexHandle, Vec<HalfedgeHandle>)> = self
+            .vertices()
+            .iter_all()
+            .filter(|&vh| !self.is_deleted(vh))
+            .map(|vh| (vh, self.voh_ccw_iter(vh).collect::<Vec<_>>()))
+            .filter(|(_, ohs)| {
+                ohs.iter().any(|&hh| is_flipped(self, hh))
+                    && ohs.iter().any(|&hh| !is_flipped(self, hh))
+            })
+            .collect();
+
+        let from_prev: Vec<(VertexHandle, HalfedgeHandle)> = hhs
+            .iter()
+            .map(|&hh| (self.from_vertex(hh), self.prev_halfedge(hh)))
+            .collect();
+        let mut src: Vec<HalfedgeHandle> = self.halfedges().iter_all().collect();
+        for (&hh, &(from, prev)) in hhs.iter().zip(&from_prev) {
+            src[hh.index_us()] = prev;
+            let halfedge = self.halfedge_item_mut(hh);
+            halfedge.vh = from;
+            std::mem::swap(&mut halfedge.hnext, &mut halfedge.hprev);
+        }
+        self.h_props = self.h_props.gather(&src);
+
+        for (vh, ohs) in &mixed {
+            // The boundary halfedges into and out of each fan, found by rotating clockwise from
+            // the outgoing one across the faces of the fan, whose links are intact.
+            let mut fans = Vec::new();
+            for &hh in ohs {
+                let out = if is_flipped(self, hh) {
+                    self.opposite_halfedge(hh)
+                } else {
+                    hh
+                };
+                if !self.is_boundary_halfedge(out) {
+                    continue;
+                }
+                let mut into = self.opposite_halfedge(out);
+                while !self.is_boundary_halfedge(into) {
+                    into = self.opposite_halfedge(self.next_halfedge(into));
+                }
+                fans.push((into, out));
+            }
+            for i in 0..fans.len() {
+                self.set_next_halfedge(fans[i].0, fans[(i + 1) % fans.len()].1);
+            }
+            self.set_vertex_halfedge(*vh, fans[0].1);
+        }
+
+        let vhs: Vec<VertexHandle> = self
+            .vertices()
+            .iter_all()
+            .filter(|&vh| !self.is_deleted(vh))
+            .collect();
+        for vh in vhs {
+            let hh = self.vertex_halfedge(vh);
+            if hh.is_valid() && is_flipped(self, hh) && self.to_vertex(hh) == vh {
+                self.set_vertex_halfedge(vh, self.opposite_halfedge(hh));
+                self.adjust_outgoing_halfedge(vh);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::VertexHandle;
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, grid, mesh_with_vertices};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    /// Vertices of each face, starting from the smallest handle.
+    fn faces(mesh: &Mesh) -> Vec<Vec<VertexHandle>> {
+        mesh.faces()
+            .iter()
+            .map(|fh| {
+                let mut vhs: Vec<_> = mesh.fv_ccw_iter(fh).collect();
+                let min = (0..vhs.len()).min_by_key(|&i| vhs[i].index()).unwrap();
+                vhs.rotate_left(min);
+                vhs
+            })
+            .collect()
+    }
+
+    fn reversed(faces: Vec<Vec<VertexHandle>>) -> Vec<Vec<VertexHandle>> {
+        faces
+            .into_iter()
+            .map(|mut vhs| {
+                vhs[1..].reverse();
+                vhs
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reverse_orientation() {
+        let (mut mesh, _) = grid(2);
+        // Label each corner by its face and vertex.
+        let corner = |mesh: &Mesh, hh| {
+            (u64::from(mesh.face(hh).index()) << 32) | u64::from(mesh.to_vertex(hh).index())
+        };
+        let prop = mesh.h_props_mut().add::<u64>(Some("corner".into()));
+        for hh in mesh.halfedges().iter().collect::<Vec<_>>() {
+            let value = corner(&mesh, hh);
+            mesh.h_props_mut().get_mut(prop).unwrap()[hh] = value;
+        }
+        let before = faces(&mesh);
+        mesh.reverse_orientation();
+        assert_consistent(&mesh);
+        assert_eq!(faces(&mesh), reversed(before.clone()));
+        for hh in mesh.halfedges().iter() {
+            let value = corner(&mesh, hh);
+            assert_eq!(mesh.h_props().get(prop).unwrap()[hh], value);
+        }
+        assert_eq!(mesh.boundary_loops().len(), 1);
+
+        mesh.reverse_orientation();
+        assert_consistent(&mesh);
+        assert_eq!(faces(&mesh), before);
+    }
+
+    /// Two triangles on either side of the edge from (0, 0, 0) to (1, 0, 0), the second of
+    /// which disagrees with the first, so `add_face_lenient()` duplicates vertex 0 as vertex 4
+    /// and moves it by `offset` along x. Returns the mesh and the duplicate.
+    fn seam(offset: f64) -> (Mesh, Vec<VertexHandle>) {
+        let (mut mesh, mut v) = mesh_with_vertices(4);
+        mesh.request_vertex_status();
+        for (&vh, &(x, y)) in v
+            .iter()
+            .zip(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (0.0, -1.0)])
+        {
+            mesh.set_point(vh, Vec3::new(x, y, 0.0));
+        }
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        let res = mesh.add_face_lenient(&[v[0], v[1], v[3]]).unwrap();
+        let dup = VertexHandle::from_index(4);
+        assert_eq!(res.duplicated, vec![(v[0], dup)]);
+        mesh.set_point(dup, Vec3::new(offset, 0.0, 0.0));
+        v.push(dup);
+        (mesh, v)
+    }
+
+    #[test]
+    fn orient_consistently() {
+        let (mut mesh, v) = seam(0.0);
+        // A third triangle away from both.
+        let far: Vec<_> = [(5.0, 0.0), (6.0, 0.0), (5.0, 1.0)]
+            .iter()
+            .map(|&(x, y)| mesh.add_vertex(Vec3::new(x, y, 0.0)))
+            .collect();
+        mesh.add_face(&far).unwrap();
+        assert_eq!(
+            faces(&mesh),
+            vec![vec![v[0], v[1], v[2]], vec![v[1], v[3], v[4]], far.clone(),]
+        );
+        assert_eq!(mesh.orient_consistently(), Ok(vec![]));
+        assert_consistent(&mesh);
+        assert_eq!(
+            faces(&mesh),
+            vec![vec![v[0], v[1], v[2]], vec![v[1], v[4], v[3]], far.clone(),]
+        );
+        // Vertex 1 is shared by both triangles, whose fans are relinked.
+        assert_eq!(mesh.vertex_valence(v[1]), 4);
+        assert!(mesh.is_boundary(v[1]));
+
+        assert_eq!(mesh.orient_consistently(), Ok(vec![]));
+        assert_eq!(faces(&mesh)[1], vec![v[1], v[4], v[3]]);
+    }
+
+    #[test]
+    fn near_coincident_seam() {
+        // Within 1e-9 of the diagonal of the bounding box, which is sqrt(5).
+        let (mut mesh, v) = seam(1e-12);
+        assert_eq!(mesh.orient_consistently(), Ok(vec![]));
+        assert_consistent(&mesh);
+        assert_eq!(faces(&mesh)[0], vec![v[0], v[1], v[2]]);
+        assert_eq!(faces(&mesh)[1], vec![v[1], v[4], v[3]]);
+
+        // Out of tolerance, so not a seam.
+        let (mut mesh, _) = seam(1e-3);
+        let before = faces(&mesh);
+        assert_eq!(mesh.orient_consistently(), Ok(vec![]));
+        assert_eq!(faces(&mesh), before);
+    }
+
+    #[test]
+    fn touching_sheets() {
+        // Two triangles with separate vertices whose boundaries touch along the edge from
+        // (0, 0, 0) to (1, 0, 0) and disagree, but which were added as separate sheets.
+        let (mut mesh, v) = mesh_with_vertices(6);
+        mesh.request_vertex_status();
+        let points = [
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (0.0, -1.0),
+        ];
+        for (&vh, &(x, y)) in v.iter().zip(&points) {
+            mesh.set_point(vh, Vec3::new(x, y, 0.0));
+        }
+        mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        mesh.add_face(&[v[3], v[4], v[5]]).unwrap();
+        let before = faces(&mesh);
+        assert_eq!(mesh.orient_consistently(), Ok(vec![]));
+        assert_eq!(faces(&mesh), before);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn orient_consistently_fails() {
+        let (mut mesh, _) = grid(1);
+        assert_eq!(
+            mesh.orient_consistently(),
+            Err(TopologyError::MissingStatus("vertex"))
+        );
+    }
+
+    #[test]
+    fn mobius_strip() {
+        // A strip of 4 quads around the z axis, whose ends are joined with a half twist. The
+        // last quad disagrees with the first, so `add_face_lenient()` duplicates a vertex.
+        let n = 4;
+        let mut mesh = Mesh::new();
+        mesh.request_vertex_status();
+        let mut top = Vec::new();
+        let mut bottom = Vec::new();
+        for i in 0..n {
+            let angle = std::f64::consts::PI * 2.0 * i as f64 / n as f64;
+            let (x, y) = (angle.cos(), angle.sin());
+            top.push(mesh.add_vertex(Vec3::new(x, y, 1.0)));
+            bottom.push(mesh.add_vertex(Vec3::new(x, y, -1.0)));
+        }
+        for i in 0..n - 1 {
+            mesh.add_face(&[top[i], bottom[i], bottom[i + 1], top[i + 1]])
+                .unwrap();
+        }
+        let res = mesh
+            .add_face_lenient(&[top[n - 1], bottom[n - 1], top[0], bottom[0]])
+            .unwrap();
+        assert_eq!(res.duplicated.len(), 1);
+
+        let before = faces(&mesh);
+        let conflicts = mesh.orient_consistently().unwrap();
+        assert_eq!(conflicts.len(), 1);
+        let (e0, e1) = conflicts[0];
+        assert!(mesh.is_boundary(e0) && mesh.is_boundary(e1));
+        assert_ne!(e0, e1);
+        assert_eq!(faces(&mesh), before);
+        assert_consistent(&mesh);
+    }
+}
@@ -0,0 +1,147 @@
+//! Reordering of mesh items by applying permutations to their storage.
+use crate::mesh::handle_map::HandleMap;
+use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::{Handle, Index};
+
+/// The map from the old handles to their positions in `order`, if `order` lists each of the
+/// `len` handles exactly once.
+fn inverse<H: Handle>(order: &[H], len: usize) -> Result<HandleMap<H>> {
+    if order.len() != len {
+        return Err(TopologyError::InvalidPermutation);
+    }
+    let mut map = HandleMap::from_vec(vec![H::new(); len]);
+    for (i, &h) in order.iter().enumerate() {
+        if h.index_us() >= len || map.get(h).is_valid() {
+            return Err(TopologyError::InvalidPermutation);
+        }
+        map.set(h, H::from_index(i as Index));
+    }
+    Ok(map)
+}
+
+impl Mesh {
+    /// Reorders the vertices so that the `i`th vertex is the one previously at `order[i]`, and
+    /// returns the map from the old to the new handles. `order` must list every vertex,
+    /// including DELETED ones, exactly once. The vertex properties are reordered along with the
+    /// vertices, and the halfedges are updated to reference the new handles.
+    pub fn permute_vertices(&mut self, order: &[VertexHandle]) -> Result<HandleMap<VertexHandle>> {
+        let map = inverse(order, self.vertices.len())?;
+        self.vertices = order
+            .iter()
+            .map(|&vh| self.vertices[vh.index_us()].clone())
+            .collect();
+        self.v_props = self.v_props.gather(order);
+        for edge in &mut self.edges {
+            for halfedge in edge.0.iter_mut() {
+                halfedge.vh = map.get(halfedge.vh);
+            }
+        }
+        Ok(map)
+    }
+
+    /// Reorders the faces so that the `i`th face is the one previously at `order[i]`, and
+    /// returns the map from the old to the new handles. `order` must list every face, including
+    /// DELETED ones, exactly once. The face properties are reordered along with the faces, and
+    /// the halfedges are updated to reference the new handles.
+    pub fn permute_faces(&mut self, order: &[FaceHandle]) -> Result<HandleMap<FaceHandle>> {
+        let map = inverse(order, self.faces.len())?;
+        self.faces = order
+            .iter()
+            .map(|&fh| self.faces[fh.index_us()].clone())
+            .collect();
+        self.f_props = self.f_props.gather(order);
+        for edge in &mut self.edges {
+            for halfedge in edge.0.iter_mut() {
+                halfedge.fh = map.get(halfedge.fh);
+            }
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, grid};
+    use crate::property::Handle;
+
+    #[test]
+    fn permute_vertices() {
+        let (mut mesh, v) = grid(2);
+        let prop = mesh.v_props_mut().add::<u32>(Some("index".into()));
+        for &vh in &v {
+            mesh.v_props_mut().get_mut(prop).unwrap()[vh] = vh.index();
+        }
+        let faces_before = mesh
+            .faces()
+            .iter()
+            .map(|fh| mesh.fv_ccw_iter(fh).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let points_before = mesh.points().to_vec();
+
+        let order = v.iter().rev().cloned().collect::<Vec<_>>();
+        let map = mesh.permute_vertices(&order).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(map.get(v[0]), v[8]);
+        assert_eq!(map.get(v[3]), v[5]);
+        for &vh in &v {
+            let new = map.get(vh);
+            assert_eq!(mesh.point(new), points_before[vh.index_us()]);
+            assert_eq!(mesh.v_props().get(prop).unwrap()[new], vh.index());
+        }
+        for (fh, mut vhs) in mesh.faces().iter().zip(faces_before) {
+            map.update(&mut vhs);
+            assert_eq!(mesh.fv_ccw_iter(fh).collect::<Vec<_>>(), vhs);
+        }
+    }
+
+    #[test]
+    fn permute_faces() {
+        let (mut mesh, _) = grid(2);
+        let prop = mesh.f_props_mut().add::<u32>(Some("index".into()));
+        for i in 0..4 {
+            mesh.f_props_mut().get_mut(prop).unwrap()[FaceHandle::from_index(i)] = i;
+        }
+        let faces_before = mesh
+            .faces()
+            .iter()
+            .map(|fh| mesh.fv_ccw_iter(fh).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let order = [2, 0, 3, 1].iter().map(|&i| FaceHandle::from_index(i));
+        let map = mesh.permute_faces(&order.collect::<Vec<_>>()).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(
+            map.get(FaceHandle::from_index(2)),
+            FaceHandle::from_index(0)
+        );
+        for (i, vhs) in faces_before.into_iter().enumerate() {
+            let fh = map.get(FaceHandle::from_index(i as u32));
+            assert_eq!(mesh.fv_ccw_iter(fh).collect::<Vec<_>>(), vhs);
+            assert_eq!(mesh.f_props().get(prop).unwrap()[fh], i as u32);
+            for hh in mesh.fh_ccw_iter(fh) {
+                assert_eq!(mesh.face(hh), fh);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_permutation() {
+        let (mut mesh, v) = grid(1);
+        let err = Err(TopologyError::InvalidPermutation);
+        assert_eq!(mesh.permute_vertices(&v[..3]), err);
+        assert_eq!(mesh.permute_vertices(&[v[0], v[1], v[2], v[2]]), err);
+        assert_eq!(
+            mesh.permute_vertices(&[v[0], v[1], v[2], VertexHandle::new()]),
+            err
+        );
+        assert_eq!(
+            mesh.permute_faces(&[FaceHandle::from_index(1)]),
+            Err(TopologyError::InvalidPermutation)
+        );
+        assert_eq!(mesh.points()[3].x, 1.0);
+    }
+}
@@ -79,6 +79,11 @@ macro_rules! impl_props {
                 self.len
             }
 
+            #[doc = "Whether there are no elements of the given type."]
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
             #[doc = "Returns the handle with the given name if any exists and corresponds to a"]
             #[doc = "property of type `T`. Otherwise, it returns an invalid handle."]
             pub fn handle<T: Value>(&self, name: &str) -> PropHandle<H, T> {
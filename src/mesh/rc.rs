@@ -74,7 +74,6 @@ macro_rules! def_prop_rc {
         }
 
         #[doc = "Gets the corresponding `Property` list mutably if it exists."]
-        #[allow(dead_code)]
         pub(crate) fn $get_fn_mut(&mut self) -> Option<&mut PropertyList<Status, $Handle>> {
             self.$props_field.get_mut(self.$rc_field.handle)
         }
@@ -0,0 +1,258 @@
+//! Spatial reordering of mesh items along a space-filling curve, so that items close to each
+//! other in space are also close to each other in storage.
+use crate::geometry::vector::Vec3;
+use crate::mesh::handle_map::ReorderMap;
+use crate::mesh::Mesh;
+
+/// Number of bits per coordinate of the grid on which the curves are evaluated.
+const BITS: u32 = 21;
+
+/// Space-filling curve for `Mesh::reorder_spatially()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpaceFillingCurve {
+    /// Z-order curve, which interleaves the bits of the coordinates. It is cheap to evaluate,
+    /// but jumps between distant cells.
+    Morton,
+    /// Hilbert curve, which only moves between adjacent cells, and so preserves locality better.
+    Hilbert,
+}
+
+/// Interleaves the lowest `BITS` bits of the coordinates, starting from the most significant
+/// bit of `x[0]`.
+fn interleave(x: [u32; 3]) -> u64 {
+    let mut code = 0;
+    for b in (0..BITS).rev() {
+        for &xi in &x {
+            code = (code << 1) | u64::from((xi >> b) & 1);
+        }
+    }
+    code
+}
+
+/// Position along the Hilbert curve of the grid cell `x`, following J. Skilling, "Programming
+/// the Hilbert curve", AIP Conference Proceedings 707, 2004.
+fn hilbert(mut x: [u32; 3]) -> u64 {
+    let m = 1 << (BITS - 1);
+
+    // Inverse undo.
+    let mut q = m;
+    while q > 1 {
+        let p = q - 1;
+        for i in 0..3 {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q >>= 1;
+    }
+
+    // Gray encode.
+    for i in 1..3 {
+        x[i] ^= x[i - 1];
+    }
+    let mut t = 0;
+    let mut q = m;
+    while q > 1 {
+        if x[2] & q != 0 {
+            t ^= q - 1;
+        }
+        q >>= 1;
+    }
+    for xi in x.iter_mut() {
+        *xi ^= t;
+    }
+    interleave(x)
+}
+
+impl SpaceFillingCurve {
+    /// Position along the curve of the grid cell `x`.
+    fn index(self, x: [u32; 3]) -> u64 {
+        match self {
+            SpaceFillingCurve::Morton => interleave(x),
+            SpaceFillingCurve::Hilbert => hilbert(x),
+        }
+    }
+}
+
+impl Mesh {
+    /// Reorders the vertices by the position of their points along `curve`, and the faces by
+    /// that of their centroids, through `Mesh::permute_vertices()` and `Mesh::permute_faces()`.
+    /// Returns the maps from the old to the new handles.
+    ///
+    /// The curve is evaluated on a grid of 2^21 cells along each side of the bounding cube of
+    /// the vertices that are not DELETED. Items in the same cell keep their relative order, and
+    /// DELETED items are moved to the end.
+    pub fn reorder_spatially(&mut self, curve: SpaceFillingCurve) -> ReorderMap {
+        let (min, max) = self
+            .vertices()
+            .iter_all()
+            .filter(|&vh| !self.is_deleted(vh))
+            .map(|vh| self.point(vh))
+            .fold(
+                (Vec3::repeat(f64::INFINITY), Vec3::repeat(f64::NEG_INFINITY)),
+                |(min, max), p| (min.inf(&p), max.sup(&p)),
+            );
+        let size = (max - min).max();
+        let scale = if size > 0.0 {
+            f64::from(1u32 << BITS) / size
+        } else {
+            0.0
+        };
+        let index = |p: Vec3<f64>| {
+            let cell = |x: f64| ((x * scale) as u32).min((1 << BITS) - 1);
+            let d = p - min;
+            curve.index([cell(d.x), cell(d.y), cell(d.z)])
+        };
+
+        let mut vhs: Vec<_> = self
+            .vertices()
+            .iter_all()
+            .map(|vh| {
+                let deleted = self.is_deleted(vh);
+                let key = if deleted { 0 } else { index(self.point(vh)) };
+                ((deleted, key), vh)
+            })
+            .collect();
+        vhs.sort_by_key(|&(key, _)| key);
+        let mut fhs: Vec<_> = self
+            .faces()
+            .iter_all()
+            .map(|fh| {
+                let deleted = self.is_deleted(fh);
+                let key = if deleted {
+                    0
+                } else {
+                    let (sum, n) = self
+                        .fv_ccw_iter(fh)
+                        .fold((Vec3::zeros(), 0), |(sum, n), vh| {
+                            (sum + self.point(vh), n + 1)
+                        });
+                    index(sum / f64::from(n))
+                };
+                ((deleted, key), fh)
+            })
+            .collect();
+        fhs.sort_by_key(|&(key, _)| key);
+
+        let vhs: Vec<_> = vhs.into_iter().map(|(_, vh)| vh).collect();
+        let fhs: Vec<_> = fhs.into_iter().map(|(_, fh)| fh).collect();
+        ReorderMap {
+            vertices: self
+                .permute_vertices(&vhs)
+                .expect("Sorted handles are a permutation"),
+            faces: self
+                .permute_faces(&fhs)
+                .expect("Sorted handles are a permutation"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hilbert, interleave, SpaceFillingCurve};
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+    use crate::mesh::test_util::{assert_consistent, grid, request_status};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    /// Mesh with isolated vertices at the points of an `n`x`n`x`n` integer grid, in a scrambled
+    /// order.
+    fn point_cloud(n: usize) -> Mesh {
+        let mut mesh = Mesh::new();
+        let len = n * n * n;
+        for i in 0..len {
+            // 37 is coprime with the powers of 2.
+            let k = i * 37 % len;
+            let (x, y, z) = (k % n, k / n % n, k / n / n);
+            mesh.add_vertex(Vec3::new(x as f64, y as f64, z as f64));
+        }
+        mesh
+    }
+
+    #[test]
+    fn curves() {
+        assert_eq!(interleave([1, 0, 0]), 4);
+        assert_eq!(interleave([0, 1, 1]), 3);
+        assert_eq!(interleave([2, 0, 1]), 33);
+        assert_eq!(hilbert([0, 0, 0]), 0);
+        let top = 1 << 20;
+        let mut corners: Vec<_> = (0..8)
+            .map(|i| [(i & 1) * top, (i >> 1 & 1) * top, (i >> 2) * top])
+            .collect();
+        corners.sort_by_key(|&x| hilbert(x));
+        for pair in corners.windows(2) {
+            let diff = (0..3).filter(|&i| pair[0][i] != pair[1][i]).count();
+            assert_eq!(diff, 1);
+        }
+    }
+
+    #[test]
+    fn hilbert_order() {
+        let mut mesh = point_cloud(4);
+        let map = mesh.reorder_spatially(SpaceFillingCurve::Hilbert);
+        assert_eq!(map.vertices.len(), 64);
+        let points = mesh.points();
+        assert_eq!(points[0], Vec3::zeros());
+        for pair in points.windows(2) {
+            assert_eq!((pair[1] - pair[0]).norm(), 1.0);
+        }
+    }
+
+    #[test]
+    fn morton_order() {
+        let mut mesh = point_cloud(4);
+        let before = mesh.points().to_vec();
+        let map = mesh.reorder_spatially(SpaceFillingCurve::Morton);
+        for (i, p) in before.iter().enumerate() {
+            assert_eq!(
+                mesh.point(map.vertices.get(VertexHandle::from_index(i as u32))),
+                *p
+            );
+        }
+        // The first octant comes first.
+        let points = mesh.points();
+        assert!(points[..8].iter().all(|p| p.max() <= 1.0));
+        assert_eq!(points[1], Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(points[63], Vec3::repeat(3.0));
+    }
+
+    #[test]
+    fn reorder_faces() {
+        let (mut mesh, v) = grid(4);
+        request_status(&mut mesh);
+        let fh = mesh.face(mesh.find_halfedge(v[0], v[1]));
+        mesh.delete_face(fh, true).unwrap();
+        let map = mesh.reorder_spatially(SpaceFillingCurve::Hilbert);
+        assert_consistent(&mesh);
+        assert_eq!(map.faces.get(fh), FaceHandle::from_index(15));
+        assert!(mesh.is_deleted(FaceHandle::from_index(15)));
+        let last_vertex = mesh.vertices().len() - 1;
+        assert!(mesh.is_deleted(VertexHandle::from_index(last_vertex)));
+
+        // The faces in each 2x2 quadrant of the grid are contiguous.
+        let quadrants: Vec<_> = mesh
+            .faces()
+            .iter()
+            .map(|fh| {
+                let c = mesh
+                    .fv_ccw_iter(fh)
+                    .map(|vh| mesh.point(vh))
+                    .sum::<Vec3<f64>>()
+                    / 4.0;
+                (c.x < 2.0, c.y < 2.0)
+            })
+            .collect();
+        assert_eq!(quadrants.len(), 15);
+        let runs = quadrants
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .count()
+            + 1;
+        assert_eq!(runs, 4);
+    }
+}
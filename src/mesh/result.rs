@@ -0,0 +1,93 @@
+//! Result type for mesh topology operations.
+
+use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+use std::error;
+use std::fmt;
+use std::result;
+
+/// Topology error returned by mesh edit operations that would otherwise corrupt the mesh.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TopologyError {
+    /// A face needs at least 3 distinct vertices.
+    DegenerateFace,
+    /// A vertex handle is invalid or out of bounds.
+    InvalidVertex(VertexHandle),
+    /// A face handle is invalid, out of bounds, or DELETED.
+    InvalidFace(FaceHandle),
+    /// The vertex is not on the boundary, so no more faces can be attached to it.
+    ComplexVertex(VertexHandle),
+    /// The edge between the vertices already has faces on both sides.
+    ComplexEdge(VertexHandle, VertexHandle),
+    /// The face's orientation is inconsistent with the existing face at the edge between the
+    /// vertices.
+    FlippedOrientation(VertexHandle, VertexHandle),
+    /// There is no free boundary gap around the vertex into which the face can be inserted.
+    PatchRelinkingFailed(VertexHandle),
+    /// The operation requires the status property of the named item type ("vertex", "edge",
+    /// etc.), which has not been requested.
+    MissingStatus(&'static str),
+    /// The edge cannot be flipped. See `Mesh::is_flip_ok()`.
+    FlipNotOk(EdgeHandle),
+    /// The vertex to insert into the mesh already has edges.
+    VertexNotIsolated(VertexHandle),
+    /// The halfedges do not bound the same face.
+    HalfedgesNotInFace(HalfedgeHandle, HalfedgeHandle),
+    /// An edge between the vertices already exists.
+    DuplicateEdge(VertexHandle, VertexHandle),
+    /// There is no halfedge from the first vertex to the second.
+    MissingHalfedge(VertexHandle, VertexHandle),
+    /// The halfedge is not a boundary halfedge, or its edge is DELETED.
+    HalfedgeNotBoundary(HalfedgeHandle),
+    /// The face is not a triangle, as required by `TriMesh`.
+    NonTriangleFace(FaceHandle),
+    /// The named property of the mesh being appended has no property of the same name and type
+    /// in the target mesh.
+    UnmatchedProperty(String),
+    /// The handles do not list every item of their type exactly once.
+    InvalidPermutation,
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match *self {
+            TopologyError::DegenerateFace => "Face has fewer than 3 distinct vertices".fmt(f),
+            TopologyError::InvalidVertex(vh) => write!(f, "Invalid vertex {}", vh),
+            TopologyError::InvalidFace(fh) => write!(f, "Invalid face {}", fh),
+            TopologyError::ComplexVertex(vh) => write!(f, "Complex vertex {}", vh),
+            TopologyError::ComplexEdge(v0, v1) => write!(f, "Complex edge ({}, {})", v0, v1),
+            TopologyError::FlippedOrientation(v0, v1) => {
+                write!(f, "Flipped face orientation at edge ({}, {})", v0, v1)
+            }
+            TopologyError::PatchRelinkingFailed(vh) => {
+                write!(f, "Patch re-linking failed at vertex {}", vh)
+            }
+            TopologyError::MissingStatus(item) => write!(f, "Missing {} status", item),
+            TopologyError::FlipNotOk(eh) => write!(f, "Edge {} cannot be flipped", eh),
+            TopologyError::VertexNotIsolated(vh) => write!(f, "Vertex {} is not isolated", vh),
+            TopologyError::HalfedgesNotInFace(h0, h1) => {
+                write!(f, "Halfedges {} and {} are not in the same face", h0, h1)
+            }
+            TopologyError::DuplicateEdge(v0, v1) => {
+                write!(f, "Edge ({}, {}) already exists", v0, v1)
+            }
+            TopologyError::MissingHalfedge(v0, v1) => {
+                write!(f, "Missing halfedge from {} to {}", v0, v1)
+            }
+            TopologyError::HalfedgeNotBoundary(hh) => {
+                write!(f, "Halfedge {} is not on the boundary", hh)
+            }
+            TopologyError::NonTriangleFace(fh) => write!(f, "Face {} is not a triangle", fh),
+            TopologyError::UnmatchedProperty(ref name) => {
+                write!(f, "No property matching {} in the target mesh", name)
+            }
+            TopologyError::InvalidPermutation => {
+                "Handles are not a permutation of the items".fmt(f)
+            }
+        }
+    }
+}
+
+impl error::Error for TopologyError {}
+
+/// Mesh topology operation result.
+pub type Result<T> = result::Result<T, TopologyError>;
@@ -0,0 +1,362 @@
+//! Edge and face splits, in the spirit of OpenMesh's `PolyConnectivity::split()`,
+//! `PolyConnectivity::insert_edge()`, and `TriConnectivity::split()`.
+use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+impl Mesh {
+    /// Fails unless `vh` is a valid vertex without any edges.
+    fn check_isolated(&self, vh: VertexHandle) -> Result<()> {
+        if !self.vertices().is_valid(vh) {
+            Err(TopologyError::InvalidVertex(vh))
+        } else if self.vertex_halfedge(vh).is_valid() {
+            Err(TopologyError::VertexNotIsolated(vh))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Splits the edge `eh` at the isolated vertex `vh`. Incident triangles are split into two
+    /// triangles each by connecting `vh` to their opposite vertex, while `vh` is simply inserted
+    /// into incident polygons with more vertices.
+    ///
+    /// `eh` keeps the half towards the to-vertex of its halfedge 0. The new edges copy the
+    /// properties of `eh`, the new halfedges along `eh` copy those of the halfedges of `eh` with
+    /// the same orientation, and the new faces copy those of the triangle they were split from.
+    ///
+    /// Fails without changing the mesh if `vh` is invalid or not isolated.
+    pub fn split_edge(&mut self, eh: EdgeHandle, vh: VertexHandle) -> Result<()> {
+        self.check_isolated(vh)?;
+        let h0 = self.edge_halfedge(eh, 0);
+        let h1 = self.edge_halfedge(eh, 1);
+        let is_triangle = |mesh: &Mesh, hh| {
+            let fh = mesh.face(hh);
+            fh.is_valid() && mesh.face_valence(fh) == 3
+        };
+        let (triangle0, triangle1) = (is_triangle(self, h0), is_triangle(self, h1));
+
+        let new_h0 = self.insert_vertex(eh, vh);
+        let new_h1 = self.opposite_halfedge(new_h0);
+        self.e_props.copy_all(eh, self.edge(new_h0));
+        self.h_props.copy_all(h0, new_h0);
+        self.h_props.copy_all(h1, new_h1);
+
+        // Connect `vh` to the tips of the triangles, which are now quads.
+        if triangle0 {
+            let hh = self.insert_edge_unchecked(new_h0, self.prev_halfedge(new_h0));
+            self.e_props.copy_all(eh, self.edge(hh));
+        }
+        if triangle1 {
+            let hh = self.insert_edge_unchecked(h1, self.prev_halfedge(h1));
+            self.e_props.copy_all(eh, self.edge(hh));
+        }
+        Ok(())
+    }
+
+    /// Inserts the isolated vertex `vh` into the edge `eh` without adding any other edges. The
+    /// from-vertex of halfedge 0 of `eh` is connected to `vh` via a new edge, and the new halfedge
+    /// from that vertex to `vh` is returned.
+    pub(crate) fn insert_vertex(&mut self, eh: EdgeHandle, vh: VertexHandle) -> HalfedgeHandle {
+        let h0 = self.edge_halfedge(eh, 0);
+        let h1 = self.edge_halfedge(eh, 1);
+        let vfrom = self.from_vertex(h0);
+        let ph0 = self.prev_halfedge(h0);
+        let nh1 = self.next_halfedge(h1);
+        let (f0, f1) = (self.face(h0), self.face(h1));
+
+        let new_h0 = self.new_edge(vfrom, vh);
+        let new_h1 = self.opposite_halfedge(new_h0);
+        self.set_to_vertex(h1, vh);
+
+        self.set_next_halfedge(new_h0, h0);
+        self.set_next_halfedge(h1, new_h1);
+        self.set_next_halfedge(ph0, new_h0);
+        self.set_next_halfedge(new_h1, nh1);
+        self.set_face(new_h0, f0);
+        self.set_face(new_h1, f1);
+
+        self.set_vertex_halfedge(vh, h0);
+        self.adjust_outgoing_halfedge(vh);
+        if self.vertex_halfedge(vfrom) == h0 {
+            self.set_vertex_halfedge(vfrom, new_h0);
+            self.adjust_outgoing_halfedge(vfrom);
+        }
+        new_h0
+    }
+
+    /// Splits the face `fh` into a fan of triangles around the isolated vertex `vh`, one per edge
+    /// of `fh`. `fh` keeps the triangle at its halfedge, and the new faces copy its properties.
+    ///
+    /// Fails without changing the mesh if `vh` is invalid or not isolated.
+    pub fn split_face(&mut self, fh: FaceHandle, vh: VertexHandle) -> Result<()> {
+        self.check_isolated(vh)?;
+        let hend = self.face_halfedge(fh);
+        let mut hh = self.next_halfedge(hend);
+
+        let hold = self.new_edge(self.to_vertex(hend), vh);
+        self.set_next_halfedge(hend, hold);
+        self.set_face(hold, fh);
+        let mut hold = self.opposite_halfedge(hold);
+
+        while hh != hend {
+            let hnext = self.next_halfedge(hh);
+            let fnew = self.new_face();
+            self.f_props.copy_all(fh, fnew);
+            self.set_face_halfedge(fnew, hh);
+
+            let hnew = self.new_edge(self.to_vertex(hh), vh);
+            self.set_next_halfedge(hnew, hold);
+            self.set_next_halfedge(hold, hh);
+            self.set_next_halfedge(hh, hnew);
+            self.set_face(hnew, fnew);
+            self.set_face(hold, fnew);
+            self.set_face(hh, fnew);
+
+            hold = self.opposite_halfedge(hnew);
+            hh = hnext;
+        }
+
+        self.set_next_halfedge(hold, hend);
+        self.set_next_halfedge(self.next_halfedge(hend), hold);
+        self.set_face(hold, fh);
+        self.set_vertex_halfedge(vh, hold);
+        Ok(())
+    }
+
+    /// Cuts the face containing both `prev_hh` and `next_hh` in two by inserting an edge from the
+    /// to-vertex of `prev_hh` to the from-vertex of `next_hh`, and returns the new halfedge in
+    /// that direction. That halfedge belongs to a new face consisting of the halfedges from
+    /// `next_hh` through `prev_hh`, which copies the properties of the original face.
+    ///
+    /// Fails without changing the mesh if
+    ///
+    /// - the halfedges do not bound the same face,
+    /// - either resulting face would have fewer than 3 vertices, or
+    /// - the new edge would duplicate an existing one.
+    pub fn insert_edge(
+        &mut self,
+        prev_hh: HalfedgeHandle,
+        next_hh: HalfedgeHandle,
+    ) -> Result<HalfedgeHandle> {
+        let fh = self.face(prev_hh);
+        if !fh.is_valid() || fh != self.face(next_hh) {
+            return Err(TopologyError::HalfedgesNotInFace(prev_hh, next_hh));
+        }
+        if prev_hh == next_hh
+            || self.next_halfedge(prev_hh) == next_hh
+            || self.next_halfedge(self.next_halfedge(prev_hh)) == next_hh
+        {
+            return Err(TopologyError::DegenerateFace);
+        }
+        let v0 = self.to_vertex(prev_hh);
+        let v1 = self.from_vertex(next_hh);
+        if self.find_halfedge(v0, v1).is_valid() {
+            return Err(TopologyError::DuplicateEdge(v0, v1));
+        }
+        Ok(self.insert_edge_unchecked(prev_hh, next_hh))
+    }
+
+    /// Implements `insert_edge()` without any checks.
+    pub(crate) fn insert_edge_unchecked(
+        &mut self,
+        prev_hh: HalfedgeHandle,
+        next_hh: HalfedgeHandle,
+    ) -> HalfedgeHandle {
+        let old_fh = self.face(prev_hh);
+        let hh0 = self.new_edge(self.to_vertex(prev_hh), self.from_vertex(next_hh));
+        let hh1 = self.opposite_halfedge(hh0);
+        let next_prev_hh = self.next_halfedge(prev_hh);
+        let prev_next_hh = self.prev_halfedge(next_hh);
+
+        self.set_next_halfedge(prev_hh, hh0);
+        self.set_next_halfedge(hh0, next_hh);
+        self.set_next_halfedge(prev_next_hh, hh1);
+        self.set_next_halfedge(hh1, next_prev_hh);
+
+        let new_fh = self.new_face();
+        self.f_props.copy_all(old_fh, new_fh);
+        self.set_face_halfedge(new_fh, hh0);
+        let mut hh = hh0;
+        loop {
+            self.set_face(hh, new_fh);
+            hh = self.next_halfedge(hh);
+            if hh == hh0 {
+                break;
+            }
+        }
+        self.set_face(hh1, old_fh);
+        self.set_face_halfedge(old_fh, hh1);
+        hh0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, fan, grid, mesh_with_vertices};
+    use crate::property::Handle;
+
+    #[test]
+    fn split_face_triangle() {
+        let (mut mesh, v) = mesh_with_vertices(4);
+        let fh = mesh.add_face(&v[..3]).unwrap();
+        let prop = mesh.f_props_mut().add::<u32>(Some("label".into()));
+        mesh.f_props_mut().get_mut(prop).unwrap()[fh] = 7;
+
+        mesh.split_face(fh, v[3]).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 3);
+        assert_eq!(mesh.edges().len(), 6);
+        assert_eq!(mesh.vertex_valence(v[3]), 3);
+        assert!(!mesh.is_boundary(v[3]));
+        assert_eq!(mesh.vf_ccw_iter(v[3]).count(), 3);
+        for fh in mesh.faces().iter() {
+            assert_eq!(mesh.face_valence(fh), 3);
+            assert_eq!(mesh.f_props().get(prop).unwrap()[fh], 7);
+        }
+    }
+
+    #[test]
+    fn split_face_quad() {
+        let (mut mesh, v) = grid(1);
+        let vh = mesh.add_vertex(Vec3::new(0.5, 0.5, 0.0));
+        mesh.split_face(mesh.faces().iter().next().unwrap(), vh)
+            .unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 4);
+        let mut ring = mesh.vv_ccw_iter(vh).collect::<Vec<_>>();
+        ring.sort_by_key(|vh| vh.index());
+        assert_eq!(ring, v);
+
+        // The vertex is no longer isolated.
+        let fh = mesh.faces().iter().next().unwrap();
+        assert_eq!(
+            mesh.split_face(fh, vh),
+            Err(TopologyError::VertexNotIsolated(vh))
+        );
+    }
+
+    #[test]
+    fn split_edge_interior() {
+        let (mut mesh, v, _) = fan(4);
+        let vh = mesh.add_vertex(Vec3::new(0.5, 0.0, 0.0));
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[1]));
+        let prop = mesh.e_props_mut().add::<u32>(Some("label".into()));
+        mesh.e_props_mut().get_mut(prop).unwrap()[eh] = 3;
+
+        mesh.split_edge(eh, vh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 6);
+        assert_eq!(mesh.edges().len(), 11);
+        let mut ring = mesh.vv_ccw_iter(vh).collect::<Vec<_>>();
+        ring.sort_by_key(|vh| vh.index());
+        assert_eq!(ring, vec![v[0], v[1], v[2], v[4]]);
+        assert!(!mesh.find_halfedge(v[0], v[1]).is_valid());
+        for eh in mesh.ve_ccw_iter(vh) {
+            assert_eq!(mesh.e_props().get(prop).unwrap()[eh], 3);
+        }
+        for fh in mesh.faces().iter() {
+            assert_eq!(mesh.face_valence(fh), 3);
+        }
+    }
+
+    #[test]
+    fn split_edge_boundary() {
+        let (mut mesh, v, _) = fan(4);
+        let vh = mesh.add_vertex(Vec3::new(0.5, 0.0, 0.0));
+        let hh = mesh.find_halfedge(v[1], v[2]);
+        let prop = mesh.h_props_mut().add::<u32>(Some("label".into()));
+        mesh.h_props_mut().get_mut(prop).unwrap()[hh] = 1;
+        let oh = mesh.opposite_halfedge(hh);
+        mesh.h_props_mut().get_mut(prop).unwrap()[oh] = 2;
+
+        mesh.split_edge(mesh.edge(hh), vh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 5);
+        assert!(mesh.is_boundary(vh));
+        assert_eq!(mesh.vertex_valence(vh), 3);
+        let values = mesh.h_props().get(prop).unwrap();
+        let (h0, h1) = (mesh.find_halfedge(v[1], vh), mesh.find_halfedge(vh, v[2]));
+        assert_eq!((values[h0], values[h1]), (1, 1));
+        let (o0, o1) = (mesh.opposite_halfedge(h0), mesh.opposite_halfedge(h1));
+        assert_eq!((values[o0], values[o1]), (2, 2));
+    }
+
+    #[test]
+    fn split_edge_polygons() {
+        let (mut mesh, v) = grid(2);
+        let vh = mesh.add_vertex(Vec3::new(1.0, 0.5, 0.0));
+        let eh = mesh.edge(mesh.find_halfedge(v[1], v[4]));
+        mesh.split_edge(eh, vh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 4);
+        assert_eq!(mesh.vertex_valence(vh), 2);
+        for fh in mesh.vf_ccw_iter(vh) {
+            assert_eq!(mesh.face_valence(fh), 5);
+        }
+    }
+
+    #[test]
+    fn insert_edge() {
+        let (mut mesh, v) = grid(1);
+        let fh = mesh.faces().iter().next().unwrap();
+        let prop = mesh.f_props_mut().add::<u32>(Some("label".into()));
+        mesh.f_props_mut().get_mut(prop).unwrap()[fh] = 5;
+
+        // The quad is (v0, v1, v3, v2).
+        let h01 = mesh.find_halfedge(v[0], v[1]);
+        let h20 = mesh.find_halfedge(v[2], v[0]);
+        let hh = mesh.insert_edge(h01, h20).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.from_vertex(hh), v[1]);
+        assert_eq!(mesh.to_vertex(hh), v[2]);
+        assert_eq!(mesh.faces().len(), 2);
+        let new_fh = mesh.face(hh);
+        assert_ne!(new_fh, fh);
+        assert_eq!(mesh.face(mesh.opposite_halfedge(hh)), fh);
+        assert_eq!(
+            mesh.fv_ccw_iter(new_fh).collect::<Vec<_>>(),
+            vec![v[2], v[0], v[1]]
+        );
+        assert_eq!(mesh.f_props().get(prop).unwrap()[new_fh], 5);
+    }
+
+    #[test]
+    fn insert_edge_errors() {
+        let (mut mesh, v) = grid(2);
+        let h01 = mesh.find_halfedge(v[0], v[1]);
+        let h14 = mesh.find_halfedge(v[1], v[4]);
+        let h43 = mesh.find_halfedge(v[4], v[3]);
+        let h25 = mesh.find_halfedge(v[2], v[5]);
+        assert_eq!(
+            mesh.insert_edge(h01, h25),
+            Err(TopologyError::HalfedgesNotInFace(h01, h25))
+        );
+        assert_eq!(
+            mesh.insert_edge(h01, h14),
+            Err(TopologyError::DegenerateFace)
+        );
+        assert_eq!(
+            mesh.insert_edge(h01, h43),
+            Err(TopologyError::DegenerateFace)
+        );
+        let boundary = mesh.opposite_halfedge(h01);
+        assert_eq!(
+            mesh.insert_edge(boundary, h14),
+            Err(TopologyError::HalfedgesNotInFace(boundary, h14))
+        );
+
+        // A quad whose diagonal (v0, v2) already exists outside of it.
+        let (mut mesh, v) = mesh_with_vertices(5);
+        mesh.add_face(&v[..4]).unwrap();
+        mesh.add_face(&[v[2], v[0], v[4]]).unwrap();
+        let h30 = mesh.find_halfedge(v[3], v[0]);
+        let h23 = mesh.find_halfedge(v[2], v[3]);
+        assert_eq!(
+            mesh.insert_edge(h30, h23),
+            Err(TopologyError::DuplicateEdge(v[0], v[2]))
+        );
+    }
+}
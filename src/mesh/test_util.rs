@@ -0,0 +1,96 @@
+//! Mesh fixtures and consistency checks shared by the mesh edit operation tests.
+use crate::geometry::vector::Vec3;
+use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+/// Mesh with `n` isolated vertices, with vertex `i` at `(i, 0, 0)`.
+pub(crate) fn mesh_with_vertices(n: usize) -> (Mesh, Vec<VertexHandle>) {
+    let mut mesh = Mesh::new();
+    let v = (0..n)
+        .map(|i| mesh.add_vertex(Vec3::new(i as f64, 0.0, 0.0)))
+        .collect();
+    (mesh, v)
+}
+
+/// Requests the status properties for all item types.
+pub(crate) fn request_status(mesh: &mut Mesh) {
+    mesh.request_vertex_status();
+    mesh.request_halfedge_status();
+    mesh.request_edge_status();
+    mesh.request_face_status();
+}
+
+/// Closed fan of `n` triangles around vertex 0 with ring vertices `1..=n`, where face `i` is
+/// `(0, i + 1, i + 2)` (wrapping around). Vertex `i` is at `(i, 0, 0)`.
+pub(crate) fn fan(n: usize) -> (Mesh, Vec<VertexHandle>, Vec<FaceHandle>) {
+    fan_with_faces(n, n)
+}
+
+/// Like `fan(n)`, but without the last face, which leaves a gap between vertices `n` and 1.
+pub(crate) fn open_fan(n: usize) -> (Mesh, Vec<VertexHandle>, Vec<FaceHandle>) {
+    fan_with_faces(n, n - 1)
+}
+
+/// The first `count` faces of `fan(n)`.
+fn fan_with_faces(n: usize, count: usize) -> (Mesh, Vec<VertexHandle>, Vec<FaceHandle>) {
+    let (mut mesh, v) = mesh_with_vertices(n + 1);
+    let f = (0..count)
+        .map(|i| {
+            mesh.add_face(&[v[0], v[i + 1], v[(i + 1) % n + 1]])
+                .unwrap()
+        })
+        .collect();
+    (mesh, v, f)
+}
+
+/// Grid of `n` by `n` quads, with vertex `(i, j)` at index `i * (n + 1) + j` and position
+/// `(j, i, 0)`.
+pub(crate) fn grid(n: usize) -> (Mesh, Vec<VertexHandle>) {
+    let (mut mesh, v) = mesh_with_vertices((n + 1) * (n + 1));
+    for (k, &vh) in v.iter().enumerate() {
+        let (i, j) = (k / (n + 1), k % (n + 1));
+        mesh.set_point(vh, Vec3::new(j as f64, i as f64, 0.0));
+    }
+    let vh = |i: usize, j: usize| v[i * (n + 1) + j];
+    for i in 0..n {
+        for j in 0..n {
+            mesh.add_face(&[vh(i, j), vh(i, j + 1), vh(i + 1, j + 1), vh(i + 1, j)])
+                .unwrap();
+        }
+    }
+    (mesh, v)
+}
+
+/// Checks that the halfedge loops, vertex and face anchors, and face references of all items
+/// that are not DELETED are consistent, and that they only reference items that are not DELETED.
+pub(crate) fn assert_consistent(mesh: &Mesh) {
+    assert_eq!(mesh.check_topology(), vec![]);
+    for hh in mesh.halfedges().iter() {
+        let next = mesh.next_halfedge(hh);
+        assert_eq!(mesh.prev_halfedge(next), hh);
+        assert_eq!(mesh.from_vertex(next), mesh.to_vertex(hh));
+        assert_eq!(mesh.face(next), mesh.face(hh));
+        assert!(!mesh.is_deleted(next));
+        assert!(!mesh.is_deleted(mesh.edge(next)));
+        assert!(!mesh.is_deleted(mesh.to_vertex(hh)));
+        if mesh.face(hh).is_valid() {
+            assert!(!mesh.is_deleted(mesh.face(hh)));
+        }
+    }
+    for vh in mesh.vertices().iter() {
+        let hh = mesh.vertex_halfedge(vh);
+        if hh.is_valid() {
+            assert!(!mesh.is_deleted(hh));
+            assert_eq!(mesh.from_vertex(hh), vh);
+            let any_boundary = mesh.voh_ccw_iter(vh).any(|hh| mesh.is_boundary(hh));
+            assert_eq!(mesh.is_boundary(hh), any_boundary);
+        }
+    }
+    for fh in mesh.faces().iter() {
+        assert!(mesh.face_valence(fh) >= 3);
+        for hh in mesh.fh_ccw_iter(fh) {
+            assert_eq!(mesh.face(hh), fh);
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! Triangle mesh, in the spirit of OpenMesh's `TriConnectivity` and `TriMeshT`.
+use std::convert::TryFrom;
+use std::ops::Deref;
+
+use crate::geometry::vector::Vec3;
+use crate::mesh::handle_map::{GarbageCollectionMap, HandleMap, ReorderMap};
+use crate::mesh::item_handle::{EdgeHandle, FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::mesh::prop::{EPropsMut, FPropsMut, HPropsMut, MPropsMut, VPropsMut};
+use crate::mesh::reorder::SpaceFillingCurve;
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::{Handle, Size};
+
+/// Halfedge data structure whose faces are all triangles.
+///
+/// All of `Mesh`'s queries are available via `Deref`, while only the edit operations that keep
+/// every face a triangle are exposed for mutation.
+#[derive(Clone, Default)]
+pub struct TriMesh {
+    mesh: Mesh,
+}
+
+// Forwards edit operations that preserve triangles to `Mesh`.
+macro_rules! forward_mut {
+    ($(#[$attr:meta])* fn $name:ident(&mut self $(, $arg:ident: $T:ty)*) $(-> $R:ty)?) => {
+        $(#[$attr])*
+        pub fn $name(&mut self $(, $arg: $T)*) $(-> $R)? {
+            self.mesh.$name($($arg),*)
+        }
+    };
+}
+
+impl TriMesh {
+    /// Creates an empty triangle mesh.
+    pub fn new() -> TriMesh {
+        Default::default()
+    }
+
+    /// The underlying mesh.
+    pub fn as_mesh(&self) -> &Mesh {
+        &self.mesh
+    }
+
+    /// Converts into the underlying mesh.
+    pub fn into_mesh(self) -> Mesh {
+        self.mesh
+    }
+
+    forward_mut!(
+        /// See `Mesh::reserve()`.
+        fn reserve(&mut self, nv: Size, ne: Size, nf: Size)
+    );
+    forward_mut!(
+        /// See `Mesh::clear()`.
+        fn clear(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::add_vertex()`.
+        fn add_vertex(&mut self, p: Vec3<f64>) -> VertexHandle
+    );
+    forward_mut!(
+        /// See `Mesh::set_point()`.
+        fn set_point(&mut self, vh: VertexHandle, p: Vec3<f64>)
+    );
+    forward_mut!(
+        /// See `Mesh::points_mut()`.
+        fn points_mut(&mut self) -> &mut [Vec3<f64>]
+    );
+    forward_mut!(
+        /// See `Mesh::v_props_mut()`.
+        fn v_props_mut(&mut self) -> VPropsMut<'_>
+    );
+    forward_mut!(
+        /// See `Mesh::h_props_mut()`.
+        fn h_props_mut(&mut self) -> HPropsMut<'_>
+    );
+    forward_mut!(
+        /// See `Mesh::e_props_mut()`.
+        fn e_props_mut(&mut self) -> EPropsMut<'_>
+    );
+    forward_mut!(
+        /// See `Mesh::f_props_mut()`.
+        fn f_props_mut(&mut self) -> FPropsMut<'_>
+    );
+    forward_mut!(
+        /// See `Mesh::m_props_mut()`.
+        fn m_props_mut(&mut self) -> MPropsMut<'_>
+    );
+    forward_mut!(
+        /// See `Mesh::request_vertex_status()`.
+        fn request_vertex_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::release_vertex_status()`.
+        fn release_vertex_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::request_halfedge_status()`.
+        fn request_halfedge_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::release_halfedge_status()`.
+        fn release_halfedge_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::request_edge_status()`.
+        fn request_edge_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::release_edge_status()`.
+        fn release_edge_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::request_face_status()`.
+        fn request_face_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::release_face_status()`.
+        fn release_face_status(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::delete_vertex()`.
+        fn delete_vertex(&mut self, vh: VertexHandle, delete_isolated_vertices: bool) -> Result<()>
+    );
+    forward_mut!(
+        /// See `Mesh::delete_edge()`.
+        fn delete_edge(&mut self, eh: EdgeHandle, delete_isolated_vertices: bool) -> Result<()>
+    );
+    forward_mut!(
+        /// See `Mesh::delete_face()`.
+        fn delete_face(&mut self, fh: FaceHandle, delete_isolated_vertices: bool) -> Result<()>
+    );
+    forward_mut!(
+        /// See `Mesh::garbage_collection()`.
+        fn garbage_collection(&mut self) -> GarbageCollectionMap
+    );
+    forward_mut!(
+        /// See `Mesh::collapse()`. Collapsing an edge of a triangle mesh removes the triangles
+        /// incident to it.
+        fn collapse(&mut self, v0v1: HalfedgeHandle) -> Result<()>
+    );
+    forward_mut!(
+        /// See `Mesh::vertex_split()`.
+        fn vertex_split(
+            &mut self,
+            v0_pos: Vec3<f64>,
+            v1: VertexHandle,
+            vl: VertexHandle,
+            vr: VertexHandle
+        ) -> Result<HalfedgeHandle>
+    );
+    forward_mut!(
+        /// See `Mesh::flip()`.
+        fn flip(&mut self, eh: EdgeHandle) -> Result<()>
+    );
+    forward_mut!(
+        /// See `Mesh::split_edge()`. Each triangle incident to `eh` is split in two.
+        fn split_edge(&mut self, eh: EdgeHandle, vh: VertexHandle) -> Result<()>
+    );
+    forward_mut!(
+        /// See `Mesh::split_face()`. The triangle is split into three.
+        fn split_face(&mut self, fh: FaceHandle, vh: VertexHandle) -> Result<()>
+    );
+    forward_mut!(
+        /// See `Mesh::reverse_orientation()`.
+        fn reverse_orientation(&mut self)
+    );
+    forward_mut!(
+        /// See `Mesh::orient_consistently()`.
+        fn orient_consistently(&mut self) -> Result<Vec<(EdgeHandle, EdgeHandle)>>
+    );
+    forward_mut!(
+        /// See `Mesh::permute_vertices()`.
+        fn permute_vertices(&mut self, order: &[VertexHandle]) -> Result<HandleMap<VertexHandle>>
+    );
+    forward_mut!(
+        /// See `Mesh::permute_faces()`.
+        fn permute_faces(&mut self, order: &[FaceHandle]) -> Result<HandleMap<FaceHandle>>
+    );
+    forward_mut!(
+        /// See `Mesh::reorder_spatially()`.
+        fn reorder_spatially(&mut self, curve: SpaceFillingCurve) -> ReorderMap
+    );
+
+    /// Adds a face bounded by the given vertices in counter-clockwise order. A face with more
+    /// than 3 vertices is triangulated as by `Mesh::triangulate_face()`. Returns the handle of the
+    /// first triangle added, which is followed by the handles of the rest.
+    ///
+    /// On error, the mesh is left unchanged. See `Mesh::add_face()` for the failure cases. This
+    /// also fails if the polygon cannot be triangulated without duplicating an existing edge.
+    pub fn add_face(&mut self, vhs: &[VertexHandle]) -> Result<FaceHandle> {
+        let ears = if vhs.iter().all(|&vh| self.mesh.vertices().is_valid(vh)) {
+            self.mesh.ear_clipping(vhs)?
+        } else {
+            Vec::new()
+        };
+        let fh = self.mesh.add_face(vhs)?;
+        if !ears.is_empty() {
+            let mut hh = self.mesh.halfedge(fh);
+            while self.mesh.from_vertex(hh) != vhs[0] {
+                hh = self.mesh.next_halfedge(hh);
+            }
+            let out_hhs = vhs
+                .iter()
+                .scan(hh, |hh, _| {
+                    let out_hh = *hh;
+                    *hh = self.mesh.next_halfedge(out_hh);
+                    Some(out_hh)
+                })
+                .collect();
+            self.mesh.cut_ears(out_hhs, &ears);
+        }
+        Ok(fh)
+    }
+
+    /// The vertices of the triangle `fh` in counter-clockwise order, in the same order as
+    /// `Mesh::fv_ccw_iter()`.
+    pub fn face_vertices(&self, fh: FaceHandle) -> [VertexHandle; 3] {
+        let [h0, h1, h2] = self.face_halfedges(fh);
+        [
+            self.mesh.to_vertex(h0),
+            self.mesh.to_vertex(h1),
+            self.mesh.to_vertex(h2),
+        ]
+    }
+
+    /// The halfedges of the triangle `fh` in counter-clockwise order, starting at its halfedge.
+    pub fn face_halfedges(&self, fh: FaceHandle) -> [HalfedgeHandle; 3] {
+        let h0 = self.mesh.halfedge(fh);
+        let h1 = self.mesh.next_halfedge(h0);
+        [h0, h1, self.mesh.next_halfedge(h1)]
+    }
+
+    /// Splits the triangle `fh` into 4 by adding a vertex at the midpoint of each of its edges,
+    /// and returns these vertices in the order of `face_halfedges()`. `fh` keeps the middle
+    /// triangle, and the corner triangles copy its properties. Neighboring triangles are split in
+    /// two by connecting the new vertex to their opposite vertex.
+    ///
+    /// The edges and halfedges along the split edges copy their properties as in
+    /// `Mesh::split_edge()`.
+    ///
+    /// Fails with `TopologyError::InvalidFace` without changing the mesh if `fh` is invalid or
+    /// DELETED.
+    pub fn split_1_to_4(&mut self, fh: FaceHandle) -> Result<[VertexHandle; 3]> {
+        if !self.faces().is_valid(fh) || self.is_deleted(fh) {
+            return Err(TopologyError::InvalidFace(fh));
+        }
+        let hhs = self.face_halfedges(fh);
+        let mesh = &mut self.mesh;
+        let mut midpoints = [VertexHandle::new(); 3];
+        let mut corner_hhs = [HalfedgeHandle::new(); 3];
+        for (i, &hh) in hhs.iter().enumerate() {
+            let eh = mesh.edge(hh);
+            let h0 = mesh.edge_halfedge(eh, 0);
+            let h1 = mesh.edge_halfedge(eh, 1);
+            let p = (mesh.point(mesh.from_vertex(hh)) + mesh.point(mesh.to_vertex(hh))) * 0.5;
+            let vh = mesh.add_vertex(p);
+            let new_h0 = mesh.insert_vertex(eh, vh);
+            let new_h1 = mesh.opposite_halfedge(new_h0);
+            mesh.e_props.copy_all(eh, mesh.edge(new_h0));
+            mesh.h_props.copy_all(h0, new_h0);
+            mesh.h_props.copy_all(h1, new_h1);
+
+            // The halfedge from the corner to `vh` in `fh`, and the one into `vh` on the other
+            // side, where the neighboring triangle is now a quad.
+            let (corner_hh, other_hh) = if hh == h0 { (new_h0, h1) } else { (hh, new_h0) };
+            if mesh.face(other_hh).is_valid() {
+                let spoke = mesh.insert_edge_unchecked(other_hh, mesh.prev_halfedge(other_hh));
+                mesh.e_props.copy_all(eh, mesh.edge(spoke));
+            }
+            midpoints[i] = vh;
+            corner_hhs[i] = corner_hh;
+        }
+
+        // Cut off the corners of the hexagon.
+        for &hh in &corner_hhs {
+            let prev_hh = mesh.prev_halfedge(hh);
+            mesh.insert_edge_unchecked(hh, prev_hh);
+        }
+        Ok(midpoints)
+    }
+}
+
+impl Deref for TriMesh {
+    type Target = Mesh;
+
+    fn deref(&self) -> &Mesh {
+        &self.mesh
+    }
+}
+
+impl TryFrom<Mesh> for TriMesh {
+    type Error = TopologyError;
+
+    /// Fails with `TopologyError::NonTriangleFace` on the first face that is not DELETED and
+    /// not a triangle.
+    fn try_from(mesh: Mesh) -> Result<TriMesh> {
+        match mesh.faces().iter().find(|&fh| mesh.face_valence(fh) != 3) {
+            Some(fh) => Err(TopologyError::NonTriangleFace(fh)),
+            None => Ok(TriMesh { mesh }),
+        }
+    }
+}
+
+impl From<TriMesh> for Mesh {
+    fn from(mesh: TriMesh) -> Mesh {
+        mesh.mesh
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use super::TriMesh;
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, fan, grid, request_status};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    fn tri_mesh_with_vertices(n: usize) -> (TriMesh, Vec<VertexHandle>) {
+        let mut mesh = TriMesh::new();
+        let v = (0..n)
+            .map(|i| mesh.add_vertex(Vec3::new(i as f64, (i * i) as f64, 0.0)))
+            .collect();
+        (mesh, v)
+    }
+
+    #[test]
+    fn add_face() {
+        let (mut mesh, v) = tri_mesh_with_vertices(6);
+        let f0 = mesh.add_face(&[v[0], v[1], v[2]]).unwrap();
+        assert_eq!(mesh.face_vertices(f0), [v[0], v[1], v[2]]);
+        assert_eq!(
+            mesh.add_face(&[v[0], v[1]]),
+            Err(TopologyError::DegenerateFace)
+        );
+
+        // A pentagon is split into 3 triangles.
+        let f1 = mesh.add_face(&[v[0], v[2], v[3], v[4], v[5]]).unwrap();
+        assert_eq!(f1, FaceHandle::from_index(1));
+        assert_eq!(mesh.faces().len(), 4);
+        assert_eq!(mesh.edges().len(), 9);
+        assert_consistent(&mesh);
+        for fh in mesh.faces().iter() {
+            assert_eq!(mesh.face_valence(fh), 3);
+        }
+
+        // A concave quad is split along the diagonal inside it.
+        let (mut mesh, v) = tri_mesh_with_vertices(4);
+        mesh.set_point(v[1], Vec3::new(4.0, 2.0, 0.0));
+        mesh.set_point(v[2], Vec3::new(0.0, 4.0, 0.0));
+        mesh.set_point(v[3], Vec3::new(1.0, 2.0, 0.0));
+        mesh.add_face(&v).unwrap();
+        assert_eq!(mesh.faces().len(), 2);
+        assert!(mesh.find_halfedge(v[1], v[3]).is_valid());
+
+        // Both diagonals of the quad (v0, v1, v2, v3) already exist.
+        let (mut mesh, v) = tri_mesh_with_vertices(6);
+        mesh.add_face(&[v[0], v[4], v[2]]).unwrap();
+        mesh.add_face(&[v[1], v[5], v[3]]).unwrap();
+        assert!(matches!(
+            mesh.add_face(&v[..4]),
+            Err(TopologyError::DuplicateEdge(_, _))
+        ));
+        assert_eq!(mesh.faces().len(), 2);
+    }
+
+    #[test]
+    fn try_from_mesh() {
+        let (mesh, _, _) = fan(4);
+        let mesh = TriMesh::try_from(mesh).unwrap();
+        assert_eq!(mesh.faces().len(), 4);
+        let mesh = Mesh::from(mesh);
+        assert_eq!(mesh.faces().len(), 4);
+
+        let (mesh, _) = grid(2);
+        assert_eq!(
+            TriMesh::try_from(mesh).err(),
+            Some(TopologyError::NonTriangleFace(FaceHandle::from_index(0)))
+        );
+    }
+
+    #[test]
+    fn split_1_to_4() {
+        let (mesh, v, f) = fan(4);
+        let mut mesh = TriMesh::try_from(mesh).unwrap();
+        let e_prop = mesh.e_props_mut().add::<u32>(Some("index".into()));
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[1]));
+        mesh.e_props_mut().get_mut(e_prop).unwrap()[eh] = 7;
+
+        let midpoints = mesh.split_1_to_4(f[0]).unwrap();
+        assert_consistent(&mesh);
+        // 4 triangles for `f[0]`, 2 each for its neighbors `f[1]` and `f[3]`, and `f[2]`.
+        assert_eq!(mesh.faces().len(), 9);
+        for fh in mesh.faces().iter() {
+            assert_eq!(mesh.face_valence(fh), 3);
+        }
+        let mut middle = mesh.face_vertices(f[0]).to_vec();
+        middle.sort_by_key(|vh| vh.index());
+        assert_eq!(middle, midpoints.to_vec());
+
+        // Midpoints are placed on their edges, which are split into halves. The one on the
+        // boundary edge (v1, v2) has no spoke into a neighboring triangle.
+        let mid = |x: f64| *midpoints.iter().find(|&&vh| mesh.point(vh).x == x).unwrap();
+        let (m01, m12) = (mid(0.5), mid(1.5));
+        assert_eq!(mesh.vertex_valence(m01), 5);
+        assert_eq!(mesh.vertex_valence(m12), 4);
+        assert!(mesh.find_halfedge(m01, v[4]).is_valid());
+        let e_list = mesh.e_props().get(e_prop).unwrap();
+        for &vh in &[v[0], v[1], v[4]] {
+            assert_eq!(e_list[mesh.edge(mesh.find_halfedge(m01, vh))], 7);
+        }
+    }
+
+    #[test]
+    fn split_1_to_4_fails() {
+        let (mut mesh, _, f) = fan(4);
+        request_status(&mut mesh);
+        let mut mesh = TriMesh::try_from(mesh).unwrap();
+        for &fh in &[FaceHandle::new(), FaceHandle::from_index(4)] {
+            assert_eq!(mesh.split_1_to_4(fh), Err(TopologyError::InvalidFace(fh)));
+        }
+        mesh.delete_face(f[0], false).unwrap();
+        assert_eq!(
+            mesh.split_1_to_4(f[0]),
+            Err(TopologyError::InvalidFace(f[0]))
+        );
+        assert_eq!(mesh.vertices().len(), 5);
+        assert_consistent(&mesh);
+    }
+
+    #[test]
+    fn flip_and_split() {
+        let (mesh, v, f) = fan(4);
+        let mut mesh = TriMesh::try_from(mesh).unwrap();
+        let eh = mesh.edge(mesh.find_halfedge(v[0], v[1]));
+        mesh.flip(eh).unwrap();
+        let vh = mesh.add_vertex(Vec3::new(0.0, 0.0, 1.0));
+        mesh.split_face(f[2], vh).unwrap();
+        let vh = mesh.add_vertex(Vec3::new(0.0, 0.0, 2.0));
+        mesh.split_edge(eh, vh).unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 8);
+        for fh in mesh.faces().iter() {
+            assert_eq!(mesh.face_valence(fh), 3);
+        }
+    }
+}
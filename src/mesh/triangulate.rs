@@ -0,0 +1,300 @@
+//! Polygon triangulation by ear clipping in the best-fit plane of each face.
+use crate::geometry::plane3::Plane3d;
+use crate::geometry::vector::{Vec2, Vec3};
+use crate::mesh::item_handle::{FaceHandle, HalfedgeHandle, VertexHandle};
+use crate::mesh::result::{Result, TopologyError};
+use crate::mesh::Mesh;
+use crate::property::Handle;
+
+/// Twice the signed area of the 2D triangle `(a, b, c)`, positive if counter-clockwise.
+fn orient(a: Vec2<f64>, b: Vec2<f64>, c: Vec2<f64>) -> f64 {
+    let (ab, ac) = (b - a, c - a);
+    ab.x * ac.y - ab.y * ac.x
+}
+
+/// Normal of the polygon's best-fit plane by Newell's method, whose length is twice the polygon's
+/// area for planar polygons. It points towards the side from which the polygon is
+/// counter-clockwise, and is zero for degenerate polygons.
+pub(crate) fn newell_normal(points: &[Vec3<f64>]) -> Vec3<f64> {
+    let n = points.len();
+    let mut normal = Vec3::zeros();
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % n];
+        normal += Vec3::new(
+            (p.y - q.y) * (p.z + q.z),
+            (p.z - q.z) * (p.x + q.x),
+            (p.x - q.x) * (p.y + q.y),
+        );
+    }
+    normal
+}
+
+/// Projects the polygon onto its best-fit plane (see `newell_normal()`), so that a
+/// counter-clockwise polygon stays counter-clockwise in 2D. All points project to the origin for
+/// a degenerate polygon.
+fn project_to_plane(points: &[Vec3<f64>]) -> Vec<Vec2<f64>> {
+    let n = points.len();
+    let normal = newell_normal(points);
+    let centroid = points.iter().sum::<Vec3<f64>>() / n as f64;
+    if normal.norm() == 0.0 {
+        return vec![Vec2::zeros(); n];
+    }
+
+    let plane = Plane3d::new(normal, &centroid);
+    let normal = plane.normal();
+    let axis = normal.iamin();
+    let u = normal.cross(&Vec3::ith(axis, 1.0)).normalize();
+    let v = normal.cross(&u);
+    points
+        .iter()
+        .map(|p| {
+            let d = p - normal * plane.signed_dist(p) - centroid;
+            Vec2::new(d.dot(&u), d.dot(&v))
+        })
+        .collect()
+}
+
+impl Mesh {
+    /// Triangulates all faces that are not DELETED via `triangulate_face()`.
+    ///
+    /// Faces that cannot be triangulated are left unchanged, and the error for the first such
+    /// face is returned after all others have been triangulated.
+    pub fn triangulate(&mut self) -> Result<()> {
+        let fhs = self.faces().iter().collect::<Vec<_>>();
+        let mut result = Ok(());
+        for fh in fhs {
+            if let Err(err) = self.triangulate_face(fh) {
+                result = result.and(Err(err));
+            }
+        }
+        result
+    }
+
+    /// Triangulates the face `fh` by ear clipping in its best-fit plane, which handles concave
+    /// polygons. `fh` keeps one of the triangles, and the new faces copy its properties. Each new
+    /// edge copies the properties of the edge following it in the triangle it cuts off.
+    ///
+    /// Ears whose new edge would duplicate an existing one are avoided. Fails without changing
+    /// the mesh if every remaining ear would duplicate an edge.
+    pub fn triangulate_face(&mut self, fh: FaceHandle) -> Result<()> {
+        let hhs = self.fh_ccw_iter(fh).collect::<Vec<_>>();
+        let vhs = hhs
+            .iter()
+            .map(|&hh| self.from_vertex(hh))
+            .collect::<Vec<_>>();
+        let ears = self.ear_clipping(&vhs)?;
+        self.cut_ears(hhs, &ears);
+        Ok(())
+    }
+
+    /// Plans the triangulation of the polygon with the given vertices in counter-clockwise
+    /// order, as the sequence of ears `[a, b, c]` (indices into `vhs`) to cut off by adding an
+    /// edge from `c` to `a`, leaving the last triangle.
+    ///
+    /// A convex ear with no other vertex inside is preferred. Failing that, e.g. for degenerate
+    /// polygons, the most convex ear is used. Fails if every ear would duplicate an edge.
+    pub(crate) fn ear_clipping(&self, vhs: &[VertexHandle]) -> Result<Vec<[usize; 3]>> {
+        let points = vhs.iter().map(|&vh| self.point(vh)).collect::<Vec<_>>();
+        let p = project_to_plane(&points);
+        let mut remaining = (0..vhs.len()).collect::<Vec<_>>();
+        let mut ears = Vec::with_capacity(vhs.len().saturating_sub(3));
+        while remaining.len() > 3 {
+            let m = remaining.len();
+            let corner = |k: usize| {
+                let a = remaining[(k + m - 1) % m];
+                let c = remaining[(k + 1) % m];
+                [a, remaining[k], c]
+            };
+            let is_empty = |[a, b, c]: [usize; 3]| {
+                remaining.iter().all(|&i| {
+                    i == a
+                        || i == b
+                        || i == c
+                        || orient(p[a], p[b], p[i]) < 0.0
+                        || orient(p[b], p[c], p[i]) < 0.0
+                        || orient(p[c], p[a], p[i]) < 0.0
+                })
+            };
+
+            let mut ear = None;
+            let mut fallback: Option<(usize, f64)> = None;
+            for k in 0..m {
+                let [a, b, c] = corner(k);
+                if self.find_halfedge(vhs[a], vhs[c]).is_valid() {
+                    continue;
+                }
+                let area = orient(p[a], p[b], p[c]);
+                if area > 0.0 && is_empty([a, b, c]) {
+                    ear = Some(k);
+                    break;
+                }
+                if fallback.is_none_or(|(_, best)| area > best) {
+                    fallback = Some((k, area));
+                }
+            }
+            let k = match ear.or_else(|| fallback.map(|(k, _)| k)) {
+                Some(k) => k,
+                None => {
+                    let [a, _, c] = corner(0);
+                    return Err(TopologyError::DuplicateEdge(vhs[a], vhs[c]));
+                }
+            };
+            ears.push(corner(k));
+            remaining.remove(k);
+        }
+        Ok(ears)
+    }
+
+    /// Cuts off the `ears` planned by `ear_clipping()` from the face whose halfedges are
+    /// `out_hhs`, where `out_hhs[i]` starts at the `i`th polygon vertex.
+    pub(crate) fn cut_ears(&mut self, mut out_hhs: Vec<HalfedgeHandle>, ears: &[[usize; 3]]) {
+        for &[a, b, _] in ears {
+            let hh = self.insert_edge_unchecked(out_hhs[b], out_hhs[a]);
+            self.e_props.copy_all(self.edge(out_hhs[a]), self.edge(hh));
+            out_hhs[a] = self.opposite_halfedge(hh);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::vector::Vec3;
+    use crate::mesh::item_handle::VertexHandle;
+    use crate::mesh::result::TopologyError;
+    use crate::mesh::test_util::{assert_consistent, grid, mesh_with_vertices};
+    use crate::mesh::Mesh;
+    use crate::property::Handle;
+
+    /// Mesh with vertices at the given positions in the x-y plane.
+    fn mesh_with_points(points: &[(f64, f64)]) -> (Mesh, Vec<VertexHandle>) {
+        let (mut mesh, v) = mesh_with_vertices(points.len());
+        for (&vh, &(x, y)) in v.iter().zip(points) {
+            mesh.set_point(vh, Vec3::new(x, y, 0.0));
+        }
+        (mesh, v)
+    }
+
+    /// Checks that all faces are counter-clockwise triangles in the x-y plane.
+    fn assert_ccw_triangles(mesh: &Mesh) {
+        for fh in mesh.faces().iter() {
+            let p = mesh
+                .fv_ccw_iter(fh)
+                .map(|vh| mesh.point(vh))
+                .collect::<Vec<_>>();
+            assert_eq!(p.len(), 3);
+            assert!((p[1] - p[0]).cross(&(p[2] - p[0])).z > 0.0);
+        }
+    }
+
+    #[test]
+    fn triangulate_concave() {
+        // Dart with its reflex vertex v3, where the fan from v0 would leave the polygon.
+        let (mut mesh, v) = mesh_with_points(&[(0.0, 0.0), (4.0, 2.0), (0.0, 4.0), (1.0, 2.0)]);
+        let fh = mesh.add_face(&v).unwrap();
+        mesh.triangulate_face(fh).unwrap();
+        assert_consistent(&mesh);
+        assert_ccw_triangles(&mesh);
+        assert!(mesh.find_halfedge(v[1], v[3]).is_valid());
+        assert!(!mesh.find_halfedge(v[0], v[2]).is_valid());
+
+        // Comb with three reflex vertices.
+        let points = [
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 3.0),
+            (3.0, 3.0),
+            (3.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 3.0),
+            (1.0, 3.0),
+            (1.0, 1.0),
+            (0.0, 3.0),
+        ];
+        let (mut mesh, v) = mesh_with_points(&points);
+        let fh = mesh.add_face(&v).unwrap();
+        mesh.triangulate().unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 8);
+        assert_ccw_triangles(&mesh);
+        let area = mesh
+            .faces()
+            .iter()
+            .map(|fh| {
+                let p = mesh
+                    .fv_ccw_iter(fh)
+                    .map(|vh| mesh.point(vh))
+                    .collect::<Vec<_>>();
+                (p[1] - p[0]).cross(&(p[2] - p[0])).z / 2.0
+            })
+            .sum::<f64>();
+        assert!((area - 9.0).abs() < 1e-9);
+        assert_eq!(mesh.face_valence(fh), 3);
+
+        // The same comb, tilted out of the x-y plane.
+        let (mut tilted, v) = mesh_with_points(&points);
+        for &vh in &v {
+            let p = tilted.point(vh);
+            tilted.set_point(vh, Vec3::new(p.x, p.y * 0.6, p.y * 0.8));
+        }
+        tilted.add_face(&v).unwrap();
+        tilted.triangulate().unwrap();
+        assert_consistent(&tilted);
+        for fh in tilted.faces().iter() {
+            let p = tilted
+                .fv_ccw_iter(fh)
+                .map(|vh| tilted.point(vh))
+                .collect::<Vec<_>>();
+            let n = (p[1] - p[0]).cross(&(p[2] - p[0]));
+            assert!(n.dot(&Vec3::new(0.0, -0.8, 0.6)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn triangulate_inherits_properties() {
+        let (mut mesh, _) = grid(2);
+        let f_prop = mesh.f_props_mut().add::<u32>(Some("index".into()));
+        let e_prop = mesh.e_props_mut().add::<u32>(Some("index".into()));
+        let f_list = mesh.f_props_mut().get_mut(f_prop).unwrap();
+        for (i, value) in f_list.storage.iter_mut().enumerate() {
+            *value = i as u32 + 10;
+        }
+        let e_list = mesh.e_props_mut().get_mut(e_prop).unwrap();
+        e_list.storage.iter_mut().for_each(|value| *value = 1);
+        mesh.triangulate().unwrap();
+        assert_consistent(&mesh);
+        assert_eq!(mesh.faces().len(), 8);
+        assert_eq!(mesh.edges().len(), 16);
+        assert_ccw_triangles(&mesh);
+
+        // Each original quad and the new triangle cut from it share the property value.
+        let f_list = mesh.f_props().get(f_prop).unwrap();
+        let mut values = mesh.faces().iter().map(|fh| f_list[fh]).collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![10, 10, 11, 11, 12, 12, 13, 13]);
+        let e_list = mesh.e_props().get(e_prop).unwrap();
+        assert!(mesh.edges().iter().all(|eh| e_list[eh] == 1));
+    }
+
+    #[test]
+    fn triangulate_avoids_duplicate_edges() {
+        // The edge (v0, v2) already exists outside the square, so the diagonal must be (v1, v3).
+        let points = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (2.0, 2.0)];
+        let (mut mesh, v) = mesh_with_points(&points);
+        let fh = mesh.add_face(&v[..4]).unwrap();
+        mesh.add_face(&[v[0], v[4], v[2]]).unwrap();
+        mesh.triangulate_face(fh).unwrap();
+        assert_consistent(&mesh);
+        assert!(mesh.find_halfedge(v[1], v[3]).is_valid());
+
+        // Both diagonals of the square exist outside of it.
+        let (mut mesh, v) = mesh_with_vertices(6);
+        let fh = mesh.add_face(&v[..4]).unwrap();
+        mesh.add_face(&[v[0], v[4], v[2]]).unwrap();
+        mesh.add_face(&[v[1], v[5], v[3]]).unwrap();
+        assert!(matches!(
+            mesh.triangulate_face(fh),
+            Err(TopologyError::DuplicateEdge(_, _))
+        ));
+        assert_eq!(mesh.face_valence(fh), 4);
+    }
+}
@@ -0,0 +1,373 @@
+//! Vertex cache optimization, which orders the triangles of a mesh for rendering from an index
+//! buffer so that the GPU's post-transform vertex cache is reused as much as possible.
+//!
+//! The optimizer follows T. Forsyth, "Linear-Speed Vertex Cache Optimisation", 2006.
+use std::collections::VecDeque;
+
+use crate::mesh::handle_map::HandleMap;
+use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+use crate::mesh::tri_mesh::TriMesh;
+use crate::property::{Handle, Index};
+
+/// Score of a vertex in one of the 3 most recently used cache positions. It is lower than that
+/// of the following positions to avoid a triangle using the same edge as the last one, which
+/// would favor strips over more compact fans.
+const LAST_TRIANGLE_SCORE: f64 = 0.75;
+/// Exponent of the falloff of the score of a vertex with its cache position.
+const CACHE_DECAY_POWER: f64 = 1.5;
+/// Scale of the score boost of vertices with few triangles left, which finishes them off.
+const VALENCE_BOOST_SCALE: f64 = 2.0;
+/// Exponent of the falloff of the score boost with the number of triangles left.
+const VALENCE_BOOST_POWER: f64 = 0.5;
+
+/// Vertex cache efficiency of a triangle order, simulating a FIFO cache as used by most GPUs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VertexCacheStats {
+    /// Average cache miss ratio: the number of vertex transforms per triangle. It is between
+    /// about 0.5 for an infinite cache on large meshes and 3.
+    pub acmr: f64,
+    /// Average transform to vertex ratio: the number of vertex transforms per vertex used by the
+    /// triangles. It is 1 for an infinite cache.
+    pub atvr: f64,
+}
+
+/// Result of `TriMesh::optimize_vertex_cache()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VertexCacheOptimization {
+    /// Statistics of the original triangle order.
+    pub before: VertexCacheStats,
+    /// Statistics of the optimized triangle order.
+    pub after: VertexCacheStats,
+    /// Map from the old to the new face handles.
+    pub faces: HandleMap<FaceHandle>,
+    /// Map from the old to the new vertex handles, if the vertices were reordered.
+    pub vertices: Option<HandleMap<VertexHandle>>,
+}
+
+/// Score of a vertex at `position` in an LRU cache of `cache_size` entries, if any, with
+/// `valence` triangles left to emit.
+fn vertex_score(position: Option<usize>, valence: usize, cache_size: usize) -> f64 {
+    if valence == 0 {
+        return -1.0;
+    }
+    let cache_score = match position {
+        Some(p) if p < 3 => LAST_TRIANGLE_SCORE,
+        Some(p) if p < cache_size => {
+            let scale = 1.0 / (cache_size - 3) as f64;
+            (1.0 - (p - 3) as f64 * scale).powf(CACHE_DECAY_POWER)
+        }
+        _ => 0.0,
+    };
+    cache_score + VALENCE_BOOST_SCALE * (valence as f64).powf(-VALENCE_BOOST_POWER)
+}
+
+/// Forsyth's greedy ordering of the `triangles` given by vertex indices below `nv`, for an LRU
+/// cache of `cache_size` entries. Returns the triangle indices in the new order.
+fn forsyth(triangles: &[[usize; 3]], nv: usize, cache_size: usize) -> Vec<usize> {
+    // Triangles around each vertex.
+    let mut offsets = vec![0; nv + 1];
+    for tri in triangles {
+        for &v in tri {
+            offsets[v + 1] += 1;
+        }
+    }
+    for v in 0..nv {
+        offsets[v + 1] += offsets[v];
+    }
+    let mut vertex_tris = vec![0; offsets[nv]];
+    let mut fill = offsets.clone();
+    for (t, tri) in triangles.iter().enumerate() {
+        for &v in tri {
+            vertex_tris[fill[v]] = t;
+            fill[v] += 1;
+        }
+    }
+
+    let mut valence: Vec<usize> = (0..nv).map(|v| offsets[v + 1] - offsets[v]).collect();
+    let mut position: Vec<Option<usize>> = vec![None; nv];
+    let mut score: Vec<f64> = (0..nv)
+        .map(|v| vertex_score(None, valence[v], cache_size))
+        .collect();
+    let tri_score = |score: &[f64], t: usize| triangles[t].iter().map(|&v| score[v]).sum::<f64>();
+    let mut emitted = vec![false; triangles.len()];
+    let mut order = Vec::with_capacity(triangles.len());
+    // Vertices in LRU order, which may hold up to 3 more than the cache while it is updated.
+    let mut cache: Vec<usize> = Vec::with_capacity(cache_size.min(nv) + 3);
+    // Position up to which to scan for remaining triangles when the cache has none.
+    let mut next_unemitted = 0;
+
+    while order.len() < triangles.len() {
+        // Best triangle around the cached vertices, or else the first remaining one.
+        let best = cache
+            .iter()
+            .flat_map(|&v| &vertex_tris[offsets[v]..offsets[v + 1]])
+            .filter(|&&t| !emitted[t])
+            .map(|&t| (t, tri_score(&score, t)))
+            .fold(None, |best: Option<(usize, f64)>, (t, s)| match best {
+                Some((_, best_score)) if best_score >= s => best,
+                _ => Some((t, s)),
+            })
+            .map(|(t, _)| t);
+        let best = best.unwrap_or_else(|| {
+            while emitted[next_unemitted] {
+                next_unemitted += 1;
+            }
+            next_unemitted
+        });
+
+        emitted[best] = true;
+        order.push(best);
+        for &v in triangles[best].iter().rev() {
+            valence[v] -= 1;
+            if let Some(p) = cache.iter().position(|&u| u == v) {
+                cache.remove(p);
+            }
+            cache.insert(0, v);
+        }
+        for &v in cache.iter().skip(cache_size) {
+            position[v] = None;
+            score[v] = vertex_score(None, valence[v], cache_size);
+        }
+        cache.truncate(cache_size);
+        for (p, &v) in cache.iter().enumerate() {
+            position[v] = Some(p);
+            score[v] = vertex_score(Some(p), valence[v], cache_size);
+        }
+    }
+    order
+}
+
+impl TriMesh {
+    /// Statistics of the vertex cache efficiency when rendering the triangles that are not
+    /// DELETED or HIDDEN in the order of their handles, for a FIFO cache of `cache_size`
+    /// vertices.
+    pub fn vertex_cache_stats(&self, cache_size: usize) -> VertexCacheStats {
+        let nv = self.vertices().len() as usize;
+        let mut cache = VecDeque::with_capacity(cache_size.min(nv) + 1);
+        let mut used = vec![false; nv];
+        let (mut misses, mut n_faces, mut n_vertices) = (0u64, 0u64, 0u64);
+        for fh in self.faces().iter() {
+            n_faces += 1;
+            for vh in self.face_vertices(fh).iter() {
+                if !used[vh.index_us()] {
+                    used[vh.index_us()] = true;
+                    n_vertices += 1;
+                }
+                if !cache.contains(vh) {
+                    misses += 1;
+                    cache.push_back(*vh);
+                    if cache.len() > cache_size {
+                        cache.pop_front();
+                    }
+                }
+            }
+        }
+        if n_faces == 0 {
+            return VertexCacheStats::default();
+        }
+        VertexCacheStats {
+            acmr: misses as f64 / n_faces as f64,
+            atvr: misses as f64 / n_vertices as f64,
+        }
+    }
+
+    /// Reorders the faces to reduce the average cache miss ratio of rendering the triangles
+    /// that are not DELETED or HIDDEN in the order of their handles, using Forsyth's algorithm
+    /// for a cache of `cache_size` vertices. The other faces are moved to the end. Sizes below 4
+    /// are raised to 4, since the 3 most recently used positions are scored separately.
+    ///
+    /// If `reorder_vertices`, the vertices are then reordered by their first use, which improves
+    /// the locality of fetching them. Unused vertices are moved to the end.
+    ///
+    /// Returns the cache statistics (see `TriMesh::vertex_cache_stats()`) before and after for
+    /// the raised size, and the handle maps.
+    pub fn optimize_vertex_cache(
+        &mut self,
+        cache_size: usize,
+        reorder_vertices: bool,
+    ) -> VertexCacheOptimization {
+        let cache_size = cache_size.max(4);
+        let before = self.vertex_cache_stats(cache_size);
+        let fhs: Vec<FaceHandle> = self.faces().iter().collect();
+        let triangles: Vec<[usize; 3]> = fhs
+            .iter()
+            .map(|&fh| {
+                let [v0, v1, v2] = self.face_vertices(fh);
+                [v0.index_us(), v1.index_us(), v2.index_us()]
+            })
+            .collect();
+        let nv = self.vertices().len() as usize;
+
+        let mut is_ordered = vec![false; self.faces().len() as usize];
+        let mut face_order: Vec<FaceHandle> = forsyth(&triangles, nv, cache_size)
+            .into_iter()
+            .map(|t| fhs[t])
+            .collect();
+        for &fh in &face_order {
+            is_ordered[fh.index_us()] = true;
+        }
+        face_order.extend(
+            self.faces()
+                .iter_all()
+                .filter(|fh| !is_ordered[fh.index_us()]),
+        );
+        let faces = self
+            .permute_faces(&face_order)
+            .expect("Reordered faces are a permutation");
+
+        let vertices = if reorder_vertices {
+            let mut is_ordered = vec![false; nv];
+            let mut vertex_order = Vec::with_capacity(nv);
+            for fh in self.faces().iter() {
+                for &vh in self.face_vertices(fh).iter() {
+                    if !is_ordered[vh.index_us()] {
+                        is_ordered[vh.index_us()] = true;
+                        vertex_order.push(vh);
+                    }
+                }
+            }
+            vertex_order.extend(
+                (0..nv)
+                    .filter(|&i| !is_ordered[i])
+                    .map(|i| VertexHandle::from_index(i as Index)),
+            );
+            Some(
+                self.permute_vertices(&vertex_order)
+                    .expect("Reordered vertices are a permutation"),
+            )
+        } else {
+            None
+        };
+
+        VertexCacheOptimization {
+            before,
+            after: self.vertex_cache_stats(cache_size),
+            faces,
+            vertices,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use crate::mesh::item_handle::{FaceHandle, VertexHandle};
+    use crate::mesh::test_util::{assert_consistent, grid};
+    use crate::mesh::tri_mesh::TriMesh;
+    use crate::property::{Handle, Index};
+
+    /// Triangulated `n`x`n` grid, with its faces in a scrambled order.
+    fn scrambled_grid(n: usize) -> TriMesh {
+        let (mut mesh, _) = grid(n);
+        mesh.triangulate().unwrap();
+        let nf = mesh.faces().len();
+        // 97 is coprime with the number of faces for the sizes used here.
+        let order: Vec<_> = (0..nf)
+            .map(|i| FaceHandle::from_index(i * 97 % nf))
+            .collect();
+        mesh.permute_faces(&order).unwrap();
+        TriMesh::try_from(mesh).unwrap()
+    }
+
+    /// The vertices of each face, sorted and in the order of the face handles.
+    fn triangles(mesh: &TriMesh) -> Vec<Vec<[f64; 3]>> {
+        mesh.faces()
+            .iter()
+            .map(|fh| {
+                let mut vs: Vec<_> = mesh
+                    .face_vertices(fh)
+                    .iter()
+                    .map(|&vh| {
+                        let p = mesh.point(vh);
+                        [p.x, p.y, p.z]
+                    })
+                    .collect();
+                vs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                vs
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stats() {
+        let (mut mesh, _) = grid(1);
+        mesh.triangulate().unwrap();
+        let mesh = TriMesh::try_from(mesh).unwrap();
+        let stats = mesh.vertex_cache_stats(16);
+        assert_eq!(stats.acmr, 2.0);
+        assert_eq!(stats.atvr, 1.0);
+        let stats = mesh.vertex_cache_stats(1);
+        assert!(stats.acmr > 2.0);
+        assert_eq!(TriMesh::default().vertex_cache_stats(16).acmr, 0.0);
+    }
+
+    #[test]
+    fn optimize_faces() {
+        let mut mesh = scrambled_grid(10);
+        let mut before = triangles(&mesh);
+        let res = mesh.optimize_vertex_cache(16, false);
+        assert_consistent(&mesh);
+        assert!(res.vertices.is_none());
+        assert!(res.after.acmr < res.before.acmr);
+        assert!(res.after.acmr < 0.8, "ACMR {}", res.after.acmr);
+        assert!(res.after.atvr < 1.4, "ATVR {}", res.after.atvr);
+
+        // The same triangles, reordered.
+        let mut after = triangles(&mesh);
+        for (i, tri) in before.iter().enumerate() {
+            let new = res.faces.get(FaceHandle::from_index(i as Index));
+            assert_eq!(&after[new.index_us()], tri);
+        }
+        before.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        after.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn optimize_vertices() {
+        let mut mesh = scrambled_grid(6);
+        mesh.add_vertex(Default::default());
+        let res = mesh.optimize_vertex_cache(8, true);
+        assert_consistent(&mesh);
+        assert!(res.after.acmr < res.before.acmr);
+        let map = res.vertices.unwrap();
+        assert_eq!(
+            map.get(VertexHandle::from_index(49)),
+            VertexHandle::from_index(49)
+        );
+
+        // Vertices are numbered in the order of their first use.
+        let mut next = 0;
+        for fh in mesh.faces().iter() {
+            for vh in mesh.face_vertices(fh).iter() {
+                assert!(vh.index() <= next);
+                if vh.index() == next {
+                    next += 1;
+                }
+            }
+        }
+        assert_eq!(next, 49);
+    }
+
+    #[test]
+    fn small_cache() {
+        let mut mesh = scrambled_grid(4);
+        let mut clamped = mesh.clone();
+        let res = mesh.optimize_vertex_cache(0, false);
+        assert_consistent(&mesh);
+        assert_eq!(res, clamped.optimize_vertex_cache(4, false));
+        assert_eq!(res.after, mesh.vertex_cache_stats(4));
+    }
+
+    #[test]
+    fn infinite_cache() {
+        let mut mesh = scrambled_grid(4);
+        let stats = mesh.vertex_cache_stats(usize::MAX);
+        assert_eq!(stats.atvr, 1.0);
+        let res = mesh.optimize_vertex_cache(usize::MAX, true);
+        assert_consistent(&mesh);
+        assert_eq!(res.before, stats);
+        assert_eq!(res.after.atvr, 1.0);
+    }
+}
@@ -4,6 +4,9 @@ use crate::property::Handle;
 use crate::property::{Index, ItemHandle, Size, Value, INVALID_INDEX};
 use crate::property::{PropertyList, ResizeableProperty}; // for `PropHandle` methods
 
+/// Name of the properties added without a name.
+const UNNAMED: &str = "<unknown>";
+
 /// Contains a parallel collection of `Property` trait objects.
 #[derive(Clone, Default)]
 pub struct PropertyContainer<H: ItemHandle> {
@@ -46,7 +49,14 @@ impl<H: ItemHandle> PropertyContainer<H> {
     where
         T: Value,
     {
-        let name = name.unwrap_or_else(|| "<unknown>".to_owned());
+        let name = name.unwrap_or_else(|| UNNAMED.to_owned());
+        let pos = self.insert(Box::new(PropertyList::<T, H>::new(name, len)));
+        PropHandle::from_index(pos as Size)
+    }
+
+    /// Stores `prop` in the first free slot and returns its index.
+    /// Panics in the unlikely case that the number of properties reaches `INVALID_INDEX`.
+    fn insert(&mut self, prop: Box<dyn ResizeableProperty<Handle = H>>) -> usize {
         let pos = self.vec.iter().position(Option::is_none);
         let pos = match pos {
             Some(n) => n,
@@ -55,14 +65,14 @@ impl<H: ItemHandle> PropertyContainer<H> {
                 self.vec.len() - 1
             }
         };
-        self.vec[pos] = Some(Box::new(PropertyList::<T, H>::new(name, len)));
+        self.vec[pos] = Some(prop);
         if pos >= INVALID_INDEX as usize {
             panic!(
                 "Number of properties {} exceeds bounds {}-1",
                 pos, INVALID_INDEX
             );
         }
-        PropHandle::from_index(pos as Size)
+        pos
     }
 
     /// Returns the property at the given handle if any exists and if the return type matches.
@@ -119,7 +129,7 @@ impl<H: ItemHandle> PropertyContainer<H> {
             // Explicitly typed to catch errors since any `&mut Option` would compile successfully.
             .map(
                 |opt_prop: &mut Option<Box<dyn ResizeableProperty<Handle = H>>>| {
-                    ::std::mem::swap(opt_prop, &mut None);
+                    *opt_prop = None;
                 },
             )
             .is_some()
@@ -128,7 +138,7 @@ impl<H: ItemHandle> PropertyContainer<H> {
     /// Removes all properties.
     pub fn clear(&mut self) {
         for opt_prop in self.vec.iter_mut() {
-            ::std::mem::swap(opt_prop, &mut None);
+            *opt_prop = None;
         }
     }
 
@@ -160,6 +170,11 @@ impl<H: ItemHandle> PropertyContainer<H> {
     ////////////////////////////////////////////////////////////////////////////////
     // Collectively managing active property lists.
 
+    /// Iterates over the active property lists.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &dyn ResizeableProperty<Handle = H>> + '_ {
+        self.vec.iter().filter_map(|opt_prop| opt_prop.as_deref())
+    }
+
     /// Copies a all properties from one item to another of the same type.
     /// It may panic if either handle is invalid.
     pub fn copy_all(&mut self, h_src: H, h_dst: H) {
@@ -170,6 +185,87 @@ impl<H: ItemHandle> PropertyContainer<H> {
         }
     }
 
+    /// A container with the same properties at the same handles, whose `i`th item has a copy of
+    /// the properties of item `src[i]` of `self`. It may panic if any handle is invalid.
+    pub(crate) fn gather(&self, src: &[H]) -> Self {
+        PropertyContainer {
+            vec: self
+                .vec
+                .iter()
+                .map(|opt_prop| opt_prop.as_ref().map(|prop| prop.gather(src)))
+                .collect(),
+        }
+    }
+
+    /// For each slot of `other`, the index of the property of `self` with the same name and
+    /// element type, if any. Each property of `self` is matched at most once, and unnamed
+    /// properties are never matched, since their names do not identify them.
+    fn matches(&self, other: &Self) -> Vec<Option<usize>> {
+        let mut matched = vec![false; self.vec.len()];
+        other
+            .vec
+            .iter()
+            .map(|opt_prop| {
+                let prop = opt_prop.as_ref()?.as_property();
+                if prop.name() == UNNAMED {
+                    return None;
+                }
+                let pos = self.vec.iter().enumerate().position(|(i, opt_self_prop)| {
+                    opt_self_prop.as_ref().is_some_and(|self_prop| {
+                        let self_prop = self_prop.as_property();
+                        !matched[i]
+                            && self_prop.name() == prop.name()
+                            && self_prop.as_any().type_id() == prop.as_any().type_id()
+                    })
+                })?;
+                matched[pos] = true;
+                Some(pos)
+            })
+            .collect()
+    }
+
+    /// The names of the properties of `other` without a property of the same name and element
+    /// type in `self`, including all unnamed ones. See `append()`.
+    pub(crate) fn unmatched_names(&self, other: &Self) -> Vec<String> {
+        self.matches(other)
+            .into_iter()
+            .zip(other.vec.iter())
+            .filter_map(|(pos, opt_prop)| match (pos, opt_prop) {
+                (None, Some(prop)) => Some(prop.name().to_owned()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Appends the `other_len` items of `other` to the `len` items of `self`. Each named property
+    /// of `other` is appended to the property of `self` with the same name and element type, if
+    /// any. The others, including all unnamed ones, are added to `self` with default values for
+    /// the first `len` items if `add_unmatched`, and dropped otherwise. Properties of `self`
+    /// without a counterpart get default values for the new items.
+    pub(crate) fn append(&mut self, len: Size, other: &Self, other_len: Size, add_unmatched: bool) {
+        for (pos, opt_prop) in self.matches(other).into_iter().zip(other.vec.iter()) {
+            let prop = match opt_prop {
+                Some(prop) => prop.as_property(),
+                None => continue,
+            };
+            match pos {
+                Some(pos) => {
+                    let appended = self.vec[pos].as_mut().unwrap().extend_from(prop);
+                    debug_assert!(appended);
+                }
+                None if add_unmatched => {
+                    let mut new_prop = opt_prop.as_ref().unwrap().gather(&[]);
+                    new_prop.resize(len);
+                    let appended = new_prop.extend_from(prop);
+                    debug_assert!(appended);
+                    self.insert(new_prop);
+                }
+                None => {}
+            }
+        }
+        self.resize_all(len + other_len);
+    }
+
     /// Clears the contents of each active property list.
     pub fn clear_all(&mut self) {
         for opt_prop in self.vec.iter_mut() {
@@ -77,6 +77,7 @@ pub trait Handle:
 /// Usage examples (`S` and `T` below must be generic.
 /// - `def_handle!(MyHandle, "Documentation for `MyHandle`");`
 /// - `def_handle!(MyHandleT<S, T>, "Documentation for `MyHandleT<S, T>`");`
+///
 /// Note: This adds the `::std::any::Any` trait constraint on all the type parameters for
 /// implementing `property::Handle`.
 macro_rules! def_handle {
@@ -180,6 +181,7 @@ pub type FPropHandle<T> = PropHandle<FaceHandle, T>;
 pub type MPropHandle<T> = PropHandle<MeshHandle, T>;
 
 #[cfg(test)]
+#[allow(dead_code)]
 mod test {
     def_handle!(MyHandle, "Test Handle Trait.");
     def_handle!(MyHandleT<T>, "Test HandleT<T> Trait.");
@@ -165,11 +165,37 @@ where
         self.storage.resize(n as usize);
     }
     fn clear(&mut self) {
-        ::std::mem::swap(&mut self.storage, &mut <T as StorageFor>::Storage::new());
+        self.storage = <T as StorageFor>::Storage::new();
     }
     fn push(&mut self) {
         self.storage.push();
     }
+    fn gather(&self, src: &[H]) -> Box<dyn ResizeableProperty<Handle = H>> {
+        let mut storage = <T as StorageFor>::Storage::new();
+        storage.resize(src.len());
+        for (i, h) in src.iter().enumerate() {
+            storage.set(i, self.storage.get(h.index_us()).clone());
+        }
+        Box::new(PropertyList::<T, H> {
+            name: self.name.clone(),
+            persistent: self.persistent,
+            storage,
+            _m: ::std::marker::PhantomData,
+        })
+    }
+    fn extend_from(&mut self, other: &dyn Property<Handle = H>) -> bool {
+        match other.downcast_ref::<PropertyList<T, H>>() {
+            Some(other) => {
+                let len = self.storage.len();
+                self.storage.resize(len + other.storage.len());
+                for i in 0..other.storage.len() {
+                    self.storage.set(len + i, other.storage.get(i).clone());
+                }
+                true
+            }
+            None => false,
+        }
+    }
     fn clone_as_trait(&self) -> Box<dyn ResizeableProperty<Handle = H>> {
         Box::new(self.clone())
     }
@@ -6,6 +6,7 @@ pub use self::handle::*;
 #[macro_use]
 mod storage;
 pub use self::storage::*;
+#[allow(clippy::module_inception)]
 mod property;
 pub use self::property::*;
 
@@ -96,6 +96,14 @@ pub trait ResizeableProperty: Property {
     /// Extend the number of elements by one.
     fn push(&mut self);
 
+    /// A property with the same name and persistency whose `i`th element is a copy of element
+    /// `src[i]` of `self`. It may panic if any handle is invalid.
+    fn gather(&self, src: &[Self::Handle]) -> Box<dyn ResizeableProperty<Handle = Self::Handle>>;
+
+    /// Appends the elements of `other` if it has the same element type, and returns whether it
+    /// did.
+    fn extend_from(&mut self, other: &dyn Property<Handle = Self::Handle>) -> bool;
+
     /// Convert to a mutable `Property` trait object.
     fn as_property(&self) -> &dyn Property<Handle = Self::Handle>;
 
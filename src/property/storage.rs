@@ -1,5 +1,5 @@
+use crate::geometry::vector::{Vec2, Vec3, Vec4, Vec6};
 use crate::io::binary::{Binary, UNKNOWN_SIZE};
-//use crate::geometry::vector::{Vec2, Vec3, Vec4, Vec6};
 use crate::property::Value;
 use crate::util::bitvec::BitVec;
 use crate::util::index::{IndexSet, IndexSetUnchecked, IndexUnchecked};
@@ -29,10 +29,18 @@ pub trait Storage:
     /// Get an element at the given location.
     fn get(&self, i: usize) -> &Self::Value;
     /// Get an element at the given location without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be less than `self.len()`.
     unsafe fn get_unchecked(&self, i: usize) -> &Self::Value;
     /// Set an element at the given location.
     fn set(&mut self, i: usize, value: Self::Value);
     /// Set an element at the given location without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be less than `self.len()`.
     unsafe fn set_unchecked(&mut self, i: usize, value: Self::Value);
     /// Swap the elements at the given indices.
     fn swap(&mut self, i: usize, j: usize);
@@ -109,29 +117,24 @@ impl_vec_storage_for!(f32);
 impl_vec_storage_for!(f64);
 impl_vec_storage_for!(String);
 
-// TODO: Add this once after introducing an alternative to Default since nalgebra doesn't implement
-// it for any of its types to avoid ambiguity: https://github.com/rustsim/nalgebra/issues/201.
-//
-//macro_rules! impl_vec_storage_for_vec {
-//    ($Type:ty) => {
-//        impl_vec_storage_for!($Type);
-//        impl_vec_storage_for!(Vec2<$Type>);
-//        impl_vec_storage_for!(Vec3<$Type>);
-//        impl_vec_storage_for!(Vec4<$Type>);
-//        impl_vec_storage_for!(Vec6<$Type>);
-//    }
-//}
-//
-//impl_vec_storage_for_vec!(i8);
-//impl_vec_storage_for_vec!(i16);
-//impl_vec_storage_for_vec!(i32);
-//impl_vec_storage_for_vec!(i64);
-//impl_vec_storage_for_vec!(u8);
-//impl_vec_storage_for_vec!(u16);
-//impl_vec_storage_for_vec!(u32);
-//impl_vec_storage_for_vec!(u64);
-//impl_vec_storage_for_vec!(f32);
-//impl_vec_storage_for_vec!(f64);
+// `i64`/`u64` vectors are omitted since they have no `Binary` implementation.
+macro_rules! impl_vec_storage_for_vec {
+    ($Type:ty) => {
+        impl_vec_storage_for!(Vec2<$Type>);
+        impl_vec_storage_for!(Vec3<$Type>);
+        impl_vec_storage_for!(Vec4<$Type>);
+        impl_vec_storage_for!(Vec6<$Type>);
+    };
+}
+
+impl_vec_storage_for_vec!(i8);
+impl_vec_storage_for_vec!(i16);
+impl_vec_storage_for_vec!(i32);
+impl_vec_storage_for_vec!(u8);
+impl_vec_storage_for_vec!(u16);
+impl_vec_storage_for_vec!(u32);
+impl_vec_storage_for_vec!(f32);
+impl_vec_storage_for_vec!(f64);
 
 impl Storage for BitVec {
     type Value = bool;
@@ -148,7 +151,7 @@ impl Storage for BitVec {
         &self[i]
     }
     unsafe fn get_unchecked(&self, i: usize) -> &Self::Value {
-        <Self as IndexUnchecked<usize>>::index_unchecked(&self, i)
+        <Self as IndexUnchecked<usize>>::index_unchecked(self, i)
     }
     fn set(&mut self, i: usize, value: bool) {
         BitVec::set(self, i, value);
@@ -5,12 +5,20 @@
 /// An unsafe alternative to `std::ops::Index` for getting the value at an index.
 pub trait IndexUnchecked<Idx>: ::std::ops::Index<Idx> {
     /// Gets a value at index `index` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be within the bounds of `self`.
     unsafe fn index_unchecked(&self, index: Idx) -> &Self::Output;
 }
 
 /// An unsafe alternative to `std::ops::IndexMut` for setting the value at an index.
 pub trait IndexSetUnchecked<Idx>: ::std::ops::Index<Idx> {
     /// Moves the provided value into index `index` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be within the bounds of `self`.
     unsafe fn index_set_unchecked(&mut self, index: Idx, value: Self::Output);
 }
 
@@ -110,27 +118,27 @@ mod test {
     #[test]
     fn test_index_bitvec() {
         let mut vec = BitVec::from_bytes(&[0xef, 0xa5, 0x71]);
-        assert_eq!(vec[0], true);
-        assert_eq!(vec[4], false);
-        assert_eq!(vec[15], true);
+        assert!(vec[0]);
+        assert!(!vec[4]);
+        assert!(vec[15]);
         unsafe {
-            assert_eq!(*vec.index_unchecked(0), true);
-            assert_eq!(*vec.index_unchecked(4), false);
-            assert_eq!(*vec.index_unchecked(15), true);
+            assert!(*vec.index_unchecked(0));
+            assert!(!*vec.index_unchecked(4));
+            assert!(*vec.index_unchecked(15));
         }
 
         unsafe {
             vec.index_set_unchecked(0, false);
-            assert_eq!(*vec.index_unchecked(0), false);
+            assert!(!*vec.index_unchecked(0));
             vec.index_set_unchecked(15, false);
-            assert_eq!(*vec.index_unchecked(15), false);
+            assert!(!*vec.index_unchecked(15));
         }
         assert_eq!(vec.as_bytes(), &[0xee, 0x25, 0x71]);
 
         vec.index_set(0, true);
-        assert_eq!(vec[0], true);
+        assert!(vec[0]);
         vec.index_set(15, true);
-        assert_eq!(vec[15], true);
+        assert!(vec[15]);
         assert_eq!(vec.as_bytes(), &[0xef, 0xa5, 0x71]);
     }
 }